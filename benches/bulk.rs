@@ -0,0 +1,56 @@
+//! Benchmarks for bulk insertion into and removal from a
+//! queue.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use smallqueue::Queue;
+
+const N: usize = 4096;
+
+fn bulk_fill(c: &mut Criterion) {
+    let data = [0x5au8; N];
+    let mut q: Queue<N, u8> = Queue::default();
+    let mut group = c.benchmark_group("fill 4 KiB");
+    group.bench_function("insert loop", |b| {
+        b.iter(|| {
+            for &byte in black_box(&data) {
+                q.insert(byte).unwrap();
+            }
+            q.clear();
+        })
+    });
+    group.bench_function("copy_from_slice", |b| {
+        b.iter(|| {
+            q.copy_from_slice(black_box(&data)).unwrap();
+            q.clear();
+        })
+    });
+    group.finish();
+}
+
+fn bulk_drain(c: &mut Criterion) {
+    let data = [0x5au8; N];
+    let mut buf = [0u8; N];
+    let mut q: Queue<N, u8> = Queue::default();
+    let mut group = c.benchmark_group("drain 4 KiB");
+    group.bench_function("extract loop", |b| {
+        b.iter(|| {
+            q.copy_from_slice(&data).unwrap();
+            for byte in buf.iter_mut() {
+                *byte = q.extract().unwrap();
+            }
+            black_box(&buf);
+        })
+    });
+    group.bench_function("extract_into", |b| {
+        b.iter(|| {
+            q.copy_from_slice(&data).unwrap();
+            q.extract_into(black_box(&mut buf));
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bulk_fill, bulk_drain);
+criterion_main!(benches);
@@ -0,0 +1,213 @@
+//! Benchmarks comparing [Queue] with a pre-allocated
+//! [VecDeque] and with `heapless::Deque`, over several
+//! access patterns, capacities and value sizes.
+//!
+//! To compare another queue, implement [Fifo] for it and add
+//! a row in [bench_capacity]. To add an access pattern, write
+//! a function generic over [Fifo] and add it to [patterns].
+
+use std::collections::VecDeque;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use smallqueue::Queue;
+
+/// Number of values passed through the queue by the
+/// round-trip and producer/consumer patterns.
+const ROUNDS: usize = 1024;
+
+/// A bounded first-in first-out queue under test.
+trait Fifo<T> {
+    /// Name of the implementation, used as the benchmark
+    /// row name.
+    const NAME: &'static str;
+
+    /// Make a new empty queue with room for `capacity`
+    /// values.
+    fn make(capacity: usize) -> Self;
+
+    /// Insert `value` at the back, returning `false` if the
+    /// queue is full.
+    fn push(&mut self, value: T) -> bool;
+
+    /// Remove and return the value at the front, if any.
+    fn pop(&mut self) -> Option<T>;
+}
+
+impl<const C: usize, T> Fifo<T> for Queue<C, T> {
+    const NAME: &'static str = "smallqueue::Queue";
+
+    fn make(_capacity: usize) -> Self {
+        Queue::new()
+    }
+
+    fn push(&mut self, value: T) -> bool {
+        self.insert(value).is_ok()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.extract()
+    }
+}
+
+impl<const C: usize, T> Fifo<T> for heapless::Deque<T, C> {
+    const NAME: &'static str = "heapless::Deque";
+
+    fn make(_capacity: usize) -> Self {
+        heapless::Deque::new()
+    }
+
+    fn push(&mut self, value: T) -> bool {
+        self.push_back(value).is_ok()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+}
+
+/// A [VecDeque] allocated up front, and limited to the
+/// requested capacity so that it never grows.
+struct Bounded<T>(VecDeque<T>, usize);
+
+impl<T> Fifo<T> for Bounded<T> {
+    const NAME: &'static str = "VecDeque";
+
+    fn make(capacity: usize) -> Self {
+        Bounded(VecDeque::with_capacity(capacity), capacity)
+    }
+
+    fn push(&mut self, value: T) -> bool {
+        if self.0.len() == self.1 {
+            return false;
+        }
+        self.0.push_back(value);
+        true
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.0.pop_front()
+    }
+}
+
+/// A value type for the benchmarks.
+trait Value: Copy {
+    /// Name of the type, used in the benchmark group name.
+    const NAME: &'static str;
+
+    /// A value derived from `i`.
+    fn make(i: usize) -> Self;
+}
+
+impl Value for u8 {
+    const NAME: &'static str = "u8";
+
+    fn make(i: usize) -> Self {
+        i as u8
+    }
+}
+
+impl Value for u64 {
+    const NAME: &'static str = "u64";
+
+    fn make(i: usize) -> Self {
+        i as u64
+    }
+}
+
+/// A 64-byte value. Its contents are only ever moved.
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct Block([u8; 64]);
+
+impl Value for Block {
+    const NAME: &'static str = "[u8; 64]";
+
+    fn make(i: usize) -> Self {
+        Block([i as u8; 64])
+    }
+}
+
+/// Insert and immediately extract one value at a time.
+fn round_trip<T: Value, Q: Fifo<T>>(q: &mut Q, _capacity: usize) {
+    for i in 0..ROUNDS {
+        q.push(T::make(i));
+        black_box(q.pop());
+    }
+}
+
+/// Fill the queue, then drain it.
+fn fill_drain<T: Value, Q: Fifo<T>>(q: &mut Q, capacity: usize) {
+    for i in 0..capacity {
+        q.push(T::make(i));
+    }
+    while let Some(v) = q.pop() {
+        black_box(v);
+    }
+}
+
+/// A producer inserting one value per step and a consumer
+/// extracting on three steps out of four, draining the queue
+/// whenever the producer finds it full.
+fn producer_consumer<T: Value, Q: Fifo<T>>(q: &mut Q, _capacity: usize) {
+    for i in 0..ROUNDS {
+        if !q.push(T::make(i)) {
+            while let Some(v) = q.pop() {
+                black_box(v);
+            }
+            q.push(T::make(i));
+        }
+        if i % 4 != 3 {
+            black_box(q.pop());
+        }
+    }
+    while let Some(v) = q.pop() {
+        black_box(v);
+    }
+}
+
+/// An access pattern, run on a queue with the given
+/// capacity. It must leave the queue empty.
+type Pattern<Q> = fn(&mut Q, usize);
+
+/// The access patterns, with their names.
+fn patterns<T: Value, Q: Fifo<T>>() -> [(&'static str, Pattern<Q>); 3] {
+    [
+        ("round trip", round_trip::<T, Q>),
+        ("fill/drain", fill_drain::<T, Q>),
+        ("producer/consumer", producer_consumer::<T, Q>),
+    ]
+}
+
+/// Run every pattern on one queue implementation.
+fn bench_fifo<T: Value, Q: Fifo<T>>(c: &mut Criterion, capacity: usize) {
+    for (name, pattern) in patterns::<T, Q>() {
+        let mut q = Q::make(capacity);
+        let group = format!("{name}/{}/{capacity}", T::NAME);
+        c.benchmark_group(group)
+            .bench_function(Q::NAME, |b| b.iter(|| pattern(black_box(&mut q), capacity)));
+    }
+}
+
+/// Run every pattern on every queue implementation, for one
+/// capacity and value type.
+fn bench_capacity<const C: usize, T: Value>(c: &mut Criterion) {
+    bench_fifo::<T, Queue<C, T>>(c, C);
+    bench_fifo::<T, Bounded<T>>(c, C);
+    bench_fifo::<T, heapless::Deque<T, C>>(c, C);
+}
+
+fn compare(c: &mut Criterion) {
+    bench_capacity::<4, u8>(c);
+    bench_capacity::<4, u64>(c);
+    bench_capacity::<4, Block>(c);
+    bench_capacity::<64, u8>(c);
+    bench_capacity::<64, u64>(c);
+    bench_capacity::<64, Block>(c);
+    bench_capacity::<4096, u8>(c);
+    bench_capacity::<4096, u64>(c);
+    bench_capacity::<4096, Block>(c);
+}
+
+criterion_group!(benches, compare);
+criterion_main!(benches);
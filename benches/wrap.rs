@@ -0,0 +1,42 @@
+//! Benchmarks for index wrapping, comparing a power-of-two
+//! capacity with its neighbor and with a round decimal
+//! capacity.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use smallqueue::Queue;
+
+const ROUNDS: usize = 1024;
+
+/// Keep the queue half full while inserting and extracting
+/// `ROUNDS` bytes, so that the indices wrap repeatedly.
+fn round_trip<const C: usize>(q: &mut Queue<C, u8>) {
+    for i in 0..ROUNDS {
+        q.insert(i as u8).unwrap();
+        black_box(q.extract());
+    }
+}
+
+fn wrap(c: &mut Criterion) {
+    let mut group = c.benchmark_group("insert/extract round trip");
+    let mut q64: Queue<64, u8> = Queue::new();
+    q64.extend([0; 32]);
+    group.bench_function("capacity 64", |b| {
+        b.iter(|| round_trip(black_box(&mut q64)))
+    });
+    let mut q65: Queue<65, u8> = Queue::new();
+    q65.extend([0; 32]);
+    group.bench_function("capacity 65", |b| {
+        b.iter(|| round_trip(black_box(&mut q65)))
+    });
+    let mut q100: Queue<100, u8> = Queue::new();
+    q100.extend([0; 50]);
+    group.bench_function("capacity 100", |b| {
+        b.iter(|| round_trip(black_box(&mut q100)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, wrap);
+criterion_main!(benches);
@@ -1,7 +1,7 @@
-/// Check for memory leaks by creating a queue containing
-/// memory references and dropping it before freeing. Run
-/// this "example" with `valgrind` to verify that it is OK
-/// and leaves the heap empty on exit.
+//! Check for memory leaks by creating a queue containing
+//! memory references and dropping it before freeing. Run
+//! this "example" with `valgrind` to verify that it is OK
+//! and leaves the heap empty on exit.
 
 use smallqueue::Queue;
 
@@ -10,5 +10,9 @@ fn main() {
     for s in ["x", "y", "z"] {
         q.insert(s.to_string()).unwrap();
     }
+    let mut d = q.drain(..2);
+    d.next().unwrap();
+    drop(d);
+    q.insert("w".to_string()).unwrap();
     drop(q);
 }
@@ -0,0 +1,125 @@
+//! Apply random sequences of operations to a [Queue] and to
+//! a capacity-limited [VecDeque] model, checking that every
+//! observable result matches.
+//!
+//! Run with `cargo fuzz run model` from the repository root.
+
+#![no_main]
+
+use std::collections::VecDeque;
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use smallqueue::Queue;
+
+const C: usize = 8;
+
+#[derive(Arbitrary, Debug)]
+enum Op {
+    Insert(u8),
+    PushFront(u8),
+    ForceInsert(u8),
+    Extract,
+    PopBack,
+    Get(usize),
+    InsertAt(usize, u8),
+    Remove(usize),
+    Swap(usize, usize),
+    RotateLeft(usize),
+    RotateRight(usize),
+    Truncate(usize),
+    Drain(usize, usize),
+    Retain(u8),
+    Reverse,
+    MakeContiguous,
+    Clear,
+}
+
+fuzz_target!(|input: (Queue<C, u8>, Vec<Op>)| {
+    let (mut q, ops) = input;
+    let mut model: VecDeque<u8> = q.iter().copied().collect();
+    for op in ops {
+        let len = model.len();
+        match op {
+            Op::Insert(v) => {
+                let r = q.insert(v);
+                assert_eq!(len < C, r.is_ok());
+                if len < C {
+                    model.push_back(v);
+                }
+            }
+            Op::PushFront(v) => {
+                let r = q.push_front(v);
+                assert_eq!(len < C, r.is_ok());
+                if len < C {
+                    model.push_front(v);
+                }
+            }
+            Op::ForceInsert(v) => {
+                let evicted = if len == C { model.pop_front() } else { None };
+                model.push_back(v);
+                assert_eq!(evicted, q.force_insert(v));
+            }
+            Op::Extract => assert_eq!(model.pop_front(), q.extract()),
+            Op::PopBack => assert_eq!(model.pop_back(), q.pop_back()),
+            Op::Get(i) => assert_eq!(model.get(i), q.get(i)),
+            Op::InsertAt(i, v) => {
+                let i = i % (len + 1);
+                let r = q.insert_at(i, v);
+                assert_eq!(len < C, r.is_ok());
+                if len < C {
+                    model.insert(i, v);
+                }
+            }
+            Op::Remove(i) => assert_eq!(model.remove(i), q.remove(i)),
+            Op::Swap(i, j) => {
+                if i < len && j < len {
+                    model.swap(i, j);
+                    q.swap(i, j);
+                }
+            }
+            Op::RotateLeft(n) => {
+                let n = n % (len + 1);
+                model.rotate_left(n);
+                q.rotate_left(n);
+            }
+            Op::RotateRight(n) => {
+                let n = n % (len + 1);
+                model.rotate_right(n);
+                q.rotate_right(n);
+            }
+            Op::Truncate(n) => {
+                model.truncate(n);
+                q.truncate(n);
+            }
+            Op::Drain(a, b) => {
+                let (a, b) = (a % (len + 1), b % (len + 1));
+                let range = a.min(b)..a.max(b);
+                assert!(model.drain(range.clone()).eq(q.drain(range)));
+            }
+            Op::Retain(m) => {
+                let m = m.max(1);
+                model.retain(|v| v % m == 0);
+                q.retain(|v| v % m == 0);
+            }
+            Op::Reverse => {
+                model.make_contiguous().reverse();
+                q.reverse();
+            }
+            Op::MakeContiguous => {
+                assert_eq!(model.make_contiguous(), q.make_contiguous());
+            }
+            Op::Clear => {
+                model.clear();
+                q.clear();
+            }
+        }
+        assert_eq!(model.len(), q.len());
+        assert_eq!(model.is_empty(), q.is_empty());
+        assert_eq!(model.len() == C, q.is_full());
+        assert_eq!(model.front(), q.front());
+        assert_eq!(model.back(), q.back());
+        assert!(model.iter().eq(q.iter()));
+        assert!(model.iter().rev().eq(q.iter().rev()));
+    }
+});
@@ -0,0 +1,174 @@
+//! [alloc] support, enabled by the `alloc` feature.
+
+use alloc::{collections::VecDeque, vec::Vec};
+
+use crate::{InsertError, Queue, QueueIndex};
+
+impl<const C: usize, T, Ix: QueueIndex> Queue<C, T, Ix> {
+    /// Returns a [Vec] holding clones of the values of the
+    /// queue, from front to back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// q.copy_from_slice(&[1, 2]).unwrap();
+    /// assert_eq!(vec![1, 2], q.to_vec());
+    /// ```
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let (head, tail) = self.as_slices();
+        let mut v = Vec::with_capacity(self.len());
+        v.extend_from_slice(head);
+        v.extend_from_slice(tail);
+        v
+    }
+}
+
+/// Move the values of a queue into a [Vec], from front to
+/// back.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// let mut q: Queue<3, usize> = Queue::default();
+/// q.copy_from_slice(&[1, 2]).unwrap();
+/// assert_eq!(vec![1, 2], Vec::from(q));
+/// ```
+impl<const C: usize, T, Ix: QueueIndex> From<Queue<C, T, Ix>> for Vec<T> {
+    fn from(q: Queue<C, T, Ix>) -> Self {
+        let mut v = Vec::with_capacity(q.len());
+        v.extend(q);
+        v
+    }
+}
+
+/// Move the values of a queue into a [VecDeque], from front
+/// to back.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// use std::collections::VecDeque;
+///
+/// let mut q: Queue<3, usize> = Queue::default();
+/// q.copy_from_slice(&[1, 2]).unwrap();
+/// assert_eq!(VecDeque::from([1, 2]), VecDeque::from(q));
+/// ```
+impl<const C: usize, T, Ix: QueueIndex> From<Queue<C, T, Ix>> for VecDeque<T> {
+    fn from(q: Queue<C, T, Ix>) -> Self {
+        let mut v = VecDeque::with_capacity(q.len());
+        v.extend(q);
+        v
+    }
+}
+
+/// Move the values of a [VecDeque] into a queue, from front
+/// to back.
+///
+/// # Errors
+///
+/// Returns [InsertError] holding the unchanged deque if it is
+/// longer than the queue capacity.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// use std::collections::VecDeque;
+///
+/// let q = Queue::<3, usize>::try_from(VecDeque::from([1, 2])).unwrap();
+/// assert_eq!(q, [1, 2]);
+/// let err = Queue::<1, usize>::try_from(VecDeque::from([1, 2])).unwrap_err();
+/// assert_eq!(2, err.into_inner().len());
+/// ```
+impl<const C: usize, T, Ix: QueueIndex> TryFrom<VecDeque<T>> for Queue<C, T, Ix> {
+    type Error = InsertError<VecDeque<T>>;
+
+    fn try_from(v: VecDeque<T>) -> Result<Self, Self::Error> {
+        if v.len() > C {
+            return Err(InsertError(v));
+        }
+        let mut q = Queue::default();
+        q.extend(v);
+        Ok(q)
+    }
+}
+
+/// A queue is equal to a [VecDeque] holding equal values in
+/// the same order.
+impl<const C: usize, T: PartialEq<U>, U, Ix: QueueIndex> PartialEq<VecDeque<U>>
+    for Queue<C, T, Ix>
+{
+    fn eq(&self, other: &VecDeque<U>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<const C: usize, T: PartialEq<U>, U, Ix: QueueIndex> PartialEq<Queue<C, U, Ix>>
+    for VecDeque<T>
+{
+    fn eq(&self, other: &Queue<C, U, Ix>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+#[test]
+fn test_to_vec() {
+    use core::cell::Cell;
+
+    let mut q: Queue<4, usize> = Queue::default();
+    assert!(q.to_vec().is_empty());
+    q.copy_from_slice(&[0; 3]).unwrap();
+    q.drop_front(3);
+    q.copy_from_slice(&[1, 2, 3]).unwrap();
+    assert!(!q.is_contiguous());
+    assert_eq!(vec![1, 2, 3], q.to_vec());
+    assert_eq!(vec![1, 2, 3], Vec::from(q));
+
+    let drops = Cell::new(0);
+    let mut q: Queue<3, crate::Counted> = Queue::default();
+    for i in 0..5 {
+        q.force_insert(crate::Counted(i, &drops));
+    }
+    assert_eq!(2, drops.get());
+    let v = Vec::from(q);
+    assert_eq!(2, drops.get());
+    assert_eq!(vec![2, 3, 4], v.iter().map(|c| c.0).collect::<Vec<_>>());
+    drop(v);
+    assert_eq!(5, drops.get());
+}
+
+#[test]
+fn test_vec_deque() {
+    let mut q: Queue<4, String> = Queue::default();
+    for v in ["x", "a", "b", "c", "d"] {
+        q.force_insert(v.to_string());
+    }
+    assert!(!q.is_contiguous());
+    let v = VecDeque::from(q);
+    assert_eq!(v, ["a", "b", "c", "d"]);
+
+    // Rotate the deque storage so that it wraps.
+    let mut v = v;
+    v.pop_front();
+    v.push_back("e".to_string());
+    assert!(!v.as_slices().1.is_empty());
+    let q = Queue::<4, String>::try_from(v.clone()).unwrap();
+    assert_eq!(q, v);
+    assert_eq!(v, q);
+    assert_eq!(q, ["b", "c", "d", "e"]);
+
+    v.push_back("f".to_string());
+    let err = Queue::<4, String>::try_from(v.clone()).unwrap_err();
+    assert_eq!(v, err.into_inner());
+    let mut q = Queue::<5, String>::try_from(v.clone()).unwrap();
+    assert_eq!(q, v);
+    q.extract().unwrap();
+    assert_ne!(q, v);
+}
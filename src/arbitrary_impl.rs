@@ -0,0 +1,46 @@
+//! [arbitrary] support, enabled by the `arbitrary` feature.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{to_index, Queue, QueueIndex};
+
+/// Generates a queue of arbitrary length and values, whose
+/// values start at an arbitrary position in storage so that
+/// wrapped layouts are exercised.
+impl<'a, const C: usize, T: Arbitrary<'a>, Ix: QueueIndex> Arbitrary<'a> for Queue<C, T, Ix> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut q = Queue::default();
+        if C > 0 {
+            q.start = to_index(u.choose_index(C)?);
+        }
+        let len = u.int_in_range(0..=C)?;
+        for _ in 0..len {
+            let value = T::arbitrary(u)?;
+            // Safety: At most C values are inserted.
+            unsafe { q.push_unchecked(value) };
+        }
+        Ok(q)
+    }
+}
+
+#[test]
+fn test_arbitrary() {
+    let mut wrapped = false;
+    for seed in 0..64u8 {
+        let data: Vec<u8> = (0..32u8)
+            .map(|i| seed.wrapping_mul(37).wrapping_add(i.wrapping_mul(11)))
+            .collect();
+        let mut u = Unstructured::new(&data);
+        let q = Queue::<5, String>::arbitrary(&mut u).unwrap();
+        assert!(q.len() <= 5);
+        wrapped |= !q.is_contiguous();
+    }
+    assert!(wrapped);
+
+    let mut u = Unstructured::new(&[]);
+    let q = Queue::<5, u8>::arbitrary(&mut u).unwrap();
+    assert!(q.is_empty());
+    let mut u = Unstructured::new(&[1, 2, 3]);
+    let q = Queue::<0, u8>::arbitrary(&mut u).unwrap();
+    assert!(q.is_empty());
+}
@@ -0,0 +1,92 @@
+//! [arrayvec] support, enabled by the `arrayvec` feature.
+
+use arrayvec::{ArrayString, ArrayVec};
+
+use crate::{Queue, QueueIndex};
+
+/// Move the values of a queue into an [ArrayVec] of the same
+/// capacity, from front to back.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// use arrayvec::ArrayVec;
+///
+/// let mut q: Queue<3, usize> = Queue::default();
+/// q.copy_from_slice(&[1, 2]).unwrap();
+/// assert_eq!(&[1, 2], ArrayVec::from(q).as_slice());
+/// ```
+impl<const C: usize, T, Ix: QueueIndex> From<Queue<C, T, Ix>> for ArrayVec<T, C> {
+    fn from(q: Queue<C, T, Ix>) -> Self {
+        let mut vec = ArrayVec::new();
+        for v in q {
+            // Safety: The vector has the same capacity as the
+            // queue.
+            unsafe { vec.push_unchecked(v) };
+        }
+        vec
+    }
+}
+
+/// Move the values of an [ArrayVec] into a queue of the same
+/// capacity, in order.
+impl<const C: usize, T, Ix: QueueIndex> From<ArrayVec<T, C>> for Queue<C, T, Ix> {
+    fn from(vec: ArrayVec<T, C>) -> Self {
+        let mut q = Queue::default();
+        q.extend(vec);
+        q
+    }
+}
+
+/// Copy the UTF-8 bytes of an [ArrayString] into a byte
+/// queue of the same capacity.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// use arrayvec::ArrayString;
+///
+/// let s = ArrayString::<4>::from("hé").unwrap();
+/// assert_eq!(Queue::<4, _>::from(s), *"hé".as_bytes());
+/// ```
+impl<const C: usize, Ix: QueueIndex> From<ArrayString<C>> for Queue<C, u8, Ix> {
+    fn from(s: ArrayString<C>) -> Self {
+        let mut q = Queue::default();
+        // The string is no longer than the queue capacity.
+        q.copy_from_slice(s.as_bytes()).unwrap();
+        q
+    }
+}
+
+#[test]
+fn test_arrayvec() {
+    use core::cell::Cell;
+
+    use crate::Counted;
+
+    let drops = Cell::new(0);
+    let mut q: Queue<4, Counted> = Queue::default();
+    for i in 0..6 {
+        q.force_insert(Counted(i, &drops));
+    }
+    assert!(!q.is_contiguous());
+    drops.set(0);
+    let v = ArrayVec::from(q);
+    assert_eq!(0, drops.get());
+    assert!(v.iter().map(|c| c.0).eq(2..6));
+    let mut q = Queue::<4, _>::from(v);
+    assert_eq!(0, drops.get());
+    assert!(q.iter().map(|c| c.0).eq(2..6));
+    q.extract().unwrap();
+    let v = ArrayVec::from(q);
+    assert_eq!(3, v.len());
+    drop(v);
+    assert_eq!(4, drops.get());
+
+    let q: Queue<4, u8> = Queue::from(ArrayString::<4>::new());
+    assert!(q.is_empty());
+    let q = Queue::<4, _>::from(ArrayString::<4>::from("abcd").unwrap());
+    assert_eq!(q, *b"abcd");
+}
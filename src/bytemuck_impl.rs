@@ -0,0 +1,168 @@
+//! [bytemuck] support, enabled by the `bytemuck` feature.
+
+use core::{mem, ptr};
+
+use bytemuck::Pod;
+
+use crate::{Queue, QueueError, QueueIndex};
+
+impl<const C: usize, T: Pod, Ix: QueueIndex> Queue<C, T, Ix> {
+    /// Returns the bytes of the queue contents as a pair of
+    /// slices, as with [Queue::as_slices].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, u16> = Queue::default();
+    /// q.copy_from_slice(&[1, 2]).unwrap();
+    /// let (head, tail) = q.as_byte_slices();
+    /// assert_eq!(4, head.len());
+    /// assert!(tail.is_empty());
+    /// ```
+    pub fn as_byte_slices(&self) -> (&[u8], &[u8]) {
+        let (head, tail) = self.as_slices();
+        (bytemuck::cast_slice(head), bytemuck::cast_slice(tail))
+    }
+
+    /// Returns the bytes of the queue contents as a pair of
+    /// mutable slices, as with [Queue::as_mut_slices].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, u16> = Queue::default();
+    /// q.copy_from_slice(&[1, 2]).unwrap();
+    /// q.as_mut_byte_slices().0.fill(0xff);
+    /// assert_eq!(q, [0xffff, 0xffff]);
+    /// ```
+    pub fn as_mut_byte_slices(&mut self) -> (&mut [u8], &mut [u8]) {
+        let (head, tail) = self.as_mut_slices();
+        (
+            bytemuck::cast_slice_mut(head),
+            bytemuck::cast_slice_mut(tail),
+        )
+    }
+
+    /// Insert the values whose bytes are `src` into the back
+    /// of the queue, in order. `src` need not be aligned for
+    /// `T`, which must not be zero-sized; this is checked at
+    /// compile time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [QueueError::Overflow], without inserting
+    /// anything, if there is not room for all of the values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `src` is not a multiple of the
+    /// size of `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, u16> = Queue::default();
+    /// q.copy_from_bytes(&[1, 0, 2, 0][..]).unwrap();
+    /// assert_eq!(2, q.len());
+    /// ```
+    ///
+    /// A zero-sized value type fails to compile.
+    ///
+    /// ```compile_fail
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, ()> = Queue::default();
+    /// q.copy_from_bytes(&[]).unwrap();
+    /// ```
+    pub fn copy_from_bytes(&mut self, src: &[u8]) -> Result<(), QueueError> {
+        const { assert!(mem::size_of::<T>() != 0, "value type is zero-sized") };
+        let size = mem::size_of::<T>();
+        if !src.len().is_multiple_of(size) {
+            panic!(
+                "byte length {} is not a multiple of the value size {size}",
+                src.len()
+            );
+        }
+        let n = src.len() / size;
+        if n > self.remaining_capacity() {
+            return Err(QueueError::Overflow);
+        }
+        let (head, tail) = self.spare_capacity_mut();
+        let nhead = n.min(head.len()) * size;
+        // Safety: The spare capacity slices have room for all
+        // of src, split as computed, and cannot overlap it.
+        // Any bytes are a valid Pod value, so the slots written
+        // are initialized.
+        unsafe {
+            ptr::copy_nonoverlapping(src.as_ptr(), head.as_mut_ptr() as *mut u8, nhead);
+            ptr::copy_nonoverlapping(
+                src.as_ptr().add(nhead),
+                tail.as_mut_ptr() as *mut u8,
+                src.len() - nhead,
+            );
+            self.commit(n);
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_byte_slices() {
+    let mut q: Queue<5, u32> = Queue::default();
+    let (head, tail) = q.as_byte_slices();
+    assert!(head.is_empty() && tail.is_empty());
+    q.copy_from_slice(&[0; 3]).unwrap();
+    q.drop_front(3);
+    let values = [0x01020304, 0x05060708, 0x090a0b0c, 0x0d0e0f10];
+    let bytes: Vec<u8> = values.iter().flat_map(|v: &u32| v.to_ne_bytes()).collect();
+    // Unaligned source.
+    let mut buf = vec![0u8; bytes.len() + 1];
+    buf[1..].copy_from_slice(&bytes);
+    q.copy_from_bytes(&buf[1..]).unwrap();
+    assert!(!q.is_contiguous());
+    assert_eq!(q, values);
+    let (head, tail) = q.as_byte_slices();
+    assert_eq!((8, 8), (head.len(), tail.len()));
+    assert_eq!(bytes, [head, tail].concat());
+
+    let (head, tail) = q.as_mut_byte_slices();
+    head[..4].copy_from_slice(&7u32.to_ne_bytes());
+    tail[4..].copy_from_slice(&9u32.to_ne_bytes());
+    assert_eq!(q, [7, 0x05060708, 0x090a0b0c, 9]);
+
+    assert!(matches!(
+        q.copy_from_bytes(&[0; 8]),
+        Err(QueueError::Overflow)
+    ));
+    assert_eq!(4, q.len());
+    q.copy_from_bytes(&[]).unwrap();
+    q.copy_from_bytes(&[0xff; 4]).unwrap();
+    assert_eq!(Some(&u32::MAX), q.back());
+
+    // The byte views are native-endian, whichever that is.
+    let little = [0x04, 0x03, 0x02, 0x01, 0x08, 0x07, 0x06, 0x05];
+    let big = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+    let (native, swapped) = if cfg!(target_endian = "little") {
+        (little, big)
+    } else {
+        (big, little)
+    };
+    for (bytes, values) in [
+        (native, [0x01020304, 0x05060708]),
+        (swapped, [0x04030201, 0x08070605]),
+    ] {
+        let mut q: Queue<2, u32> = Queue::default();
+        q.copy_from_bytes(&bytes).unwrap();
+        assert_eq!(q, values);
+        assert_eq!((&bytes[..], &[][..]), q.as_byte_slices());
+    }
+}
+
+#[test]
+#[should_panic(expected = "byte length 3 is not a multiple of the value size 2")]
+fn test_copy_from_bytes_partial() {
+    let mut q: Queue<3, u16> = Queue::default();
+    let _ = q.copy_from_bytes(&[0; 3]);
+}
@@ -0,0 +1,29 @@
+//! [defmt] support, enabled by the `defmt` feature.
+
+use defmt::{write, Format, Formatter};
+
+use crate::{Queue, QueueIndex};
+
+/// Formats the values of the queue as a list from front to
+/// back, followed by the queue length and capacity.
+impl<const C: usize, T: Format, Ix: QueueIndex> Format for Queue<C, T, Ix> {
+    fn format(&self, f: Formatter) {
+        write!(f, "[");
+        for (i, v) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ");
+            }
+            write!(f, "{}", v);
+        }
+        write!(f, "] (len {=usize}, capacity {=usize})", self.len(), C);
+    }
+}
+
+#[test]
+fn test_defmt() {
+    fn assert_format<T: Format>() {}
+
+    assert_format::<Queue<3, usize>>();
+    assert_format::<Queue<3, Queue<2, u8>>>();
+    assert_format::<crate::QueueError>();
+}
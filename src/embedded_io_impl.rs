@@ -0,0 +1,89 @@
+//! [embedded_io] support, enabled by the `embedded-io`
+//! feature.
+
+use embedded_io::{Error, ErrorKind, ErrorType, Read, ReadReady, Write, WriteReady};
+
+use crate::{Queue, QueueError, QueueIndex};
+
+impl Error for QueueError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            QueueError::Overflow => ErrorKind::WriteZero,
+        }
+    }
+}
+
+impl<const C: usize, Ix: QueueIndex> ErrorType for Queue<C, u8, Ix> {
+    type Error = QueueError;
+}
+
+/// Reads bytes from the front of the queue. Reading from an
+/// empty queue returns `Ok(0)`.
+impl<const C: usize, Ix: QueueIndex> Read for Queue<C, u8, Ix> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, QueueError> {
+        Ok(self.extract_into(buf))
+    }
+}
+
+/// Writes as many bytes as fit into the back of the queue,
+/// returning the number written. Since the trait forbids
+/// returning `Ok(0)` for a non-empty write, writing to a full
+/// queue returns [QueueError::Overflow], whose kind is
+/// [ErrorKind::WriteZero].
+impl<const C: usize, Ix: QueueIndex> Write for Queue<C, u8, Ix> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, QueueError> {
+        if !buf.is_empty() && self.is_full() {
+            return Err(QueueError::Overflow);
+        }
+        let n = buf.len().min(self.remaining_capacity());
+        // Safety: There is room for the first n bytes of buf,
+        // which is borrowed separately.
+        unsafe { self.put_back(buf.as_ptr(), n) };
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<(), QueueError> {
+        Ok(())
+    }
+}
+
+impl<const C: usize, Ix: QueueIndex> ReadReady for Queue<C, u8, Ix> {
+    fn read_ready(&mut self) -> Result<bool, QueueError> {
+        Ok(!self.is_empty())
+    }
+}
+
+impl<const C: usize, Ix: QueueIndex> WriteReady for Queue<C, u8, Ix> {
+    fn write_ready(&mut self) -> Result<bool, QueueError> {
+        Ok(!self.is_full())
+    }
+}
+
+#[test]
+fn test_embedded_io() {
+    let mut q: Queue<5, u8> = Queue::default();
+    q.copy_from_slice(&[0; 3]).unwrap();
+    q.drop_front(3);
+
+    let w: &mut dyn Write<Error = QueueError> = &mut q;
+    assert_eq!(0, w.write(&[]).unwrap());
+    assert_eq!(3, w.write(b"abc").unwrap());
+    assert_eq!(2, w.write(b"defg").unwrap());
+    assert!(matches!(w.write(b"h"), Err(QueueError::Overflow)));
+    assert_eq!(0, w.write(&[]).unwrap());
+    w.flush().unwrap();
+    assert!(matches!(w.write_all(b"h"), Err(QueueError::Overflow)));
+    assert_eq!(ErrorKind::WriteZero, QueueError::Overflow.kind());
+
+    assert!(q.read_ready().unwrap());
+    assert!(!q.write_ready().unwrap());
+    let r: &mut dyn Read<Error = QueueError> = &mut q;
+    let mut buf = [0; 4];
+    assert_eq!(4, r.read(&mut buf).unwrap());
+    assert_eq!(b"abcd", &buf);
+    assert_eq!(1, r.read(&mut buf).unwrap());
+    assert_eq!(b'e', buf[0]);
+    assert_eq!(0, r.read(&mut buf).unwrap());
+    assert!(!q.read_ready().unwrap());
+    assert!(q.write_ready().unwrap());
+}
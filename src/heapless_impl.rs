@@ -0,0 +1,169 @@
+//! [heapless] support, enabled by the `heapless` feature.
+//!
+//! Conversions between types of equal capacity are [From]
+//! impls. Conversions from types of other capacities are
+//! inherent methods rather than [TryFrom] impls, which would
+//! overlap the [From] impls when the capacities are equal.
+
+use heapless::Deque;
+
+use crate::{InsertError, Queue, QueueIndex};
+
+impl<const C: usize, T, Ix: QueueIndex> Queue<C, T, Ix> {
+    /// Make a queue holding the values of a
+    /// [heapless::Deque] of any capacity, from front to back.
+    ///
+    /// # Errors
+    ///
+    /// Returns [InsertError] holding the unchanged deque if it
+    /// is longer than the queue capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut d: heapless::Deque<usize, 8> = heapless::Deque::new();
+    /// d.push_back(1).unwrap();
+    /// let q: Queue<2, usize> = Queue::try_from_deque(d).unwrap();
+    /// assert_eq!(q, [1]);
+    /// ```
+    pub fn try_from_deque<const N: usize>(
+        deque: Deque<T, N>,
+    ) -> Result<Self, InsertError<Deque<T, N>>> {
+        if deque.len() > C {
+            return Err(InsertError(deque));
+        }
+        let mut q = Queue::default();
+        q.extend(deque);
+        Ok(q)
+    }
+
+    /// Make a queue holding the values of a [heapless::Vec]
+    /// of any capacity, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [InsertError] holding the unchanged vector if
+    /// it is longer than the queue capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let v: heapless::Vec<usize, 8> = heapless::Vec::from_slice(&[1, 2, 3]).unwrap();
+    /// let err = Queue::<2, usize>::try_from_heapless_vec(v).unwrap_err();
+    /// assert_eq!(3, err.into_inner().len());
+    /// ```
+    pub fn try_from_heapless_vec<const N: usize>(
+        vec: heapless::Vec<T, N>,
+    ) -> Result<Self, InsertError<heapless::Vec<T, N>>> {
+        if vec.len() > C {
+            return Err(InsertError(vec));
+        }
+        let mut q = Queue::default();
+        q.extend(vec);
+        Ok(q)
+    }
+}
+
+/// Move the values of a queue into a [heapless::Deque] of the
+/// same capacity, from front to back.
+impl<const C: usize, T, Ix: QueueIndex> From<Queue<C, T, Ix>> for Deque<T, C> {
+    fn from(q: Queue<C, T, Ix>) -> Self {
+        let mut deque = Deque::new();
+        for v in q {
+            // Safety: The deque has the same capacity as the
+            // queue.
+            unsafe { deque.push_back_unchecked(v) };
+        }
+        deque
+    }
+}
+
+/// Move the values of a [heapless::Deque] into a queue of the
+/// same capacity, from front to back.
+impl<const C: usize, T, Ix: QueueIndex> From<Deque<T, C>> for Queue<C, T, Ix> {
+    fn from(deque: Deque<T, C>) -> Self {
+        let mut q = Queue::default();
+        q.extend(deque);
+        q
+    }
+}
+
+/// Move the values of a queue into a [heapless::Vec] of the
+/// same capacity, from front to back.
+impl<const C: usize, T, Ix: QueueIndex> From<Queue<C, T, Ix>> for heapless::Vec<T, C> {
+    fn from(q: Queue<C, T, Ix>) -> Self {
+        let mut vec = heapless::Vec::new();
+        for v in q {
+            // Safety: The vector has the same capacity as the
+            // queue.
+            unsafe { vec.push_unchecked(v) };
+        }
+        vec
+    }
+}
+
+/// Move the values of a [heapless::Vec] into a queue of the
+/// same capacity, in order.
+impl<const C: usize, T, Ix: QueueIndex> From<heapless::Vec<T, C>> for Queue<C, T, Ix> {
+    fn from(vec: heapless::Vec<T, C>) -> Self {
+        let mut q = Queue::default();
+        q.extend(vec);
+        q
+    }
+}
+
+#[test]
+fn test_heapless() {
+    use core::cell::Cell;
+
+    use crate::Counted;
+
+    let drops = Cell::new(0);
+    let mut q: Queue<4, Counted> = Queue::default();
+    for i in 0..6 {
+        q.force_insert(Counted(i, &drops));
+    }
+    assert!(!q.is_contiguous());
+    drops.set(0);
+    let mut d = Deque::from(q);
+    assert_eq!(0, drops.get());
+    assert!(d.iter().map(|c| c.0).eq(2..6));
+
+    // Wrap the deque.
+    d.pop_front().unwrap();
+    d.push_back(Counted(6, &drops)).unwrap();
+    let q = Queue::<4, _>::from(d);
+    assert_eq!(1, drops.get());
+    assert!(q.iter().map(|c| c.0).eq(3..7));
+
+    let v = heapless::Vec::from(q);
+    assert!(v.iter().map(|c| c.0).eq(3..7));
+    let q: Queue<4, Counted, u8> = Queue::from(v);
+    assert!(q.iter().map(|c| c.0).eq(3..7));
+    assert_eq!(1, drops.get());
+
+    let mut d: Deque<Counted, 8> = Deque::new();
+    for i in 0..5 {
+        d.push_back(Counted(i, &drops)).unwrap();
+    }
+    let d = Queue::<4, Counted>::try_from_deque(d)
+        .unwrap_err()
+        .into_inner();
+    assert_eq!(1, drops.get());
+    assert_eq!(5, d.len());
+    let q = Queue::<5, Counted>::try_from_deque(d).unwrap();
+    assert!(q.iter().map(|c| c.0).eq(0..5));
+
+    let v: heapless::Vec<Counted, 8> = q.into_iter().collect();
+    let v = Queue::<4, Counted>::try_from_heapless_vec(v)
+        .unwrap_err()
+        .into_inner();
+    assert_eq!(5, v.len());
+    assert_eq!(1, drops.get());
+    let q = Queue::<8, Counted>::try_from_heapless_vec(v).unwrap();
+    assert!(q.iter().map(|c| c.0).eq(0..5));
+    drop(q);
+    assert_eq!(6, drops.get());
+}
@@ -8,6 +8,8 @@ situations.
 The queue capacity is specified at compile time using a
 "const generic" value. Internally, the queue is implemented
 using an array with a start index and a length.
+The index type defaults to `usize`; a smaller one such as
+`u8` shrinks the queue header for small capacities.
 
 This data structure is not inherently thread-safe.
 
@@ -16,24 +18,86 @@ This data structure is not inherently thread-safe.
 ```
 # use smallqueue::Queue;
 let mut q: Queue<3, usize> = Queue::default();
-q.insert(17);
-q.insert(18);
+q.insert(17).unwrap();
+q.insert(18).unwrap();
 assert_eq!(17, q.extract().unwrap());
 assert_eq!(18, q.extract().unwrap());
 assert!(q.is_empty());
 ```
+
+# Features
+
+* `alloc`: Adds conversions to and from `Vec` and
+  `VecDeque`.
+* `arbitrary`: Implements `arbitrary::Arbitrary` for
+  [Queue], for fuzzing. See also the `fuzz/` directory.
+* `arrayvec`: Adds conversions to and from
+  `arrayvec::ArrayVec`, and from `arrayvec::ArrayString`
+  to byte queues.
+* `bytemuck`: Adds byte views of queues of
+  `bytemuck::Pod` values.
+* `defmt`: Implements `defmt::Format` for [Queue] and
+  [QueueError].
+* `embedded-io`: Implements the `embedded_io` `Read`,
+  `Write`, `ReadReady` and `WriteReady` traits for byte
+  queues.
+* `heapless`: Adds conversions to and from
+  `heapless::Deque` and `heapless::Vec`.
+* `rkyv`: Implements `rkyv` archiving for [Queue], as an
+  archived vector of values from front to back.
+* `serde`: Implements `Serialize` and `Deserialize` for
+  [Queue], as a sequence of values from front to back.
+* `smallvec`: Adds conversions to and from
+  `smallvec::SmallVec`.
+* `std`: Implements `std::io::Read` and `std::io::Write`
+  for byte queues. Implies `alloc`. Without this feature
+  the crate is `no_std`.
 */
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use core::{
-    array,
-    mem::MaybeUninit,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    mem::{self, MaybeUninit},
+    ops::{Bound, Index, IndexMut, Range, RangeBounds},
     ptr,
+    slice,
 };
 
 use thiserror::Error;
 
+#[cfg(feature = "alloc")]
+mod alloc_impl;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impl;
+#[cfg(feature = "arrayvec")]
+mod arrayvec_impl;
+#[cfg(feature = "bytemuck")]
+mod bytemuck_impl;
+#[cfg(feature = "defmt")]
+mod defmt_impl;
+#[cfg(feature = "embedded-io")]
+mod embedded_io_impl;
+#[cfg(feature = "heapless")]
+mod heapless_impl;
+#[cfg(feature = "rkyv")]
+mod rkyv_impl;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "smallvec")]
+mod smallvec_impl;
+#[cfg(feature = "std")]
+mod std_impl;
+
 /// Queue errors.
 #[derive(Debug, Error)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum QueueError {
     /// An attempt was made to insert a value into a queue
     /// that was already full.
@@ -41,144 +105,5957 @@ pub enum QueueError {
     Overflow,
 }
 
+/// Error returned by an attempt to insert a value into a
+/// queue that was already full. The value that could not be
+/// inserted is handed back to the caller.
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("queue capacity exceeded")]
+pub struct InsertError<T>(pub T);
+
+impl<T> InsertError<T> {
+    /// Returns the value that could not be inserted.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<InsertError<T>> for QueueError {
+    fn from(_: InsertError<T>) -> Self {
+        QueueError::Overflow
+    }
+}
+
+/// Error returned by [Queue::try_extend] when the queue
+/// fills before the iterator is exhausted. Holds the value
+/// that was taken from the iterator but did not fit, along
+/// with the rest of the iterator.
+#[derive(Debug, Error)]
+#[error("queue capacity exceeded")]
+pub struct TryExtendError<I: Iterator> {
+    /// The value that could not be inserted.
+    pub value: I::Item,
+    /// The iterator, which has not been advanced past
+    /// `value`.
+    pub iter: I,
+}
+
+impl<I: Iterator> From<TryExtendError<I>> for QueueError {
+    fn from(_: TryExtendError<I>) -> Self {
+        QueueError::Overflow
+    }
+}
+
+/// Make a [Queue], as with `vec!`.
+///
+/// * `queue![a, b, c]` makes a full queue of the listed
+///   values, with capacity inferred as their count. See
+///   [Queue::from].
+/// * `queue![cap N; a, b]` makes a queue of capacity `N`
+///   holding the listed values. More than `N` values fail to
+///   compile. See [Queue::from_partial].
+/// * `queue![v; N]` makes a full queue of capacity `N`
+///   holding clones of `v`. See [Queue::filled].
+///
+/// The queue has the default `usize` index type.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::{queue, Queue};
+/// let q = queue![1, 2, 3];
+/// assert!(q.is_full());
+/// assert_eq!(q, [1, 2, 3]);
+///
+/// let mut q = queue![cap 4; 1, 2];
+/// q.insert(3).unwrap();
+/// assert_eq!(4, q.capacity());
+/// assert_eq!(q, [1, 2, 3]);
+///
+/// let q: Queue<0, u8> = queue![cap 0;];
+/// assert!(q.is_empty());
+///
+/// let q = queue!["x".to_string(); 3];
+/// assert!(q.is_full());
+/// assert_eq!(q, ["x", "x", "x"]);
+/// ```
+///
+/// More values than the given capacity fail to compile.
+///
+/// ```compile_fail
+/// # use smallqueue::queue;
+/// let q = queue![cap 2; 1, 2, 3];
+/// ```
+#[macro_export]
+macro_rules! queue {
+    (cap $cap:expr; $($value:expr),* $(,)?) => {
+        $crate::Queue::<{ $cap }, _>::from_partial([$($value),*])
+    };
+    ($value:expr; $n:expr) => {
+        $crate::Queue::<{ $n }, _>::filled($value)
+    };
+    ($($value:expr),* $(,)?) => {
+        $crate::Queue::<_, _>::from([$($value),*])
+    };
+}
+
+/// Integer types that can hold the internal start index and
+/// length of a [Queue], chosen by its `Ix` type parameter.
+/// This trait is sealed: it is implemented only for `u8`,
+/// `u16`, `u32` and `usize`.
+///
+/// Small queues can use a small index type to save space.
+/// A capacity too large for the index type fails to
+/// compile.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// use core::mem::size_of;
+/// assert!(size_of::<Queue<4, u8, u8>>() < size_of::<Queue<4, u8>>());
+/// ```
+///
+/// ```compile_fail
+/// # use smallqueue::Queue;
+/// let q: Queue<256, u8, u8> = Queue::new();
+/// ```
+pub trait QueueIndex: Copy + sealed::Sealed {
+    /// The largest queue capacity this type can index.
+    const MAX: usize;
+}
+
+mod sealed {
+    pub trait Sealed {
+        const ZERO: Self;
+
+        /// Convert `n`, which is at most
+        /// [QueueIndex::MAX](super::QueueIndex::MAX), from
+        /// `usize`.
+        fn from_usize(n: usize) -> Self;
+    }
+}
+
+macro_rules! queue_index {
+    ($($t:ty),*) => {
+        $(
+            impl sealed::Sealed for $t {
+                const ZERO: Self = 0;
+
+                #[inline]
+                fn from_usize(n: usize) -> Self {
+                    n as $t
+                }
+            }
+
+            impl QueueIndex for $t {
+                const MAX: usize = <$t>::MAX as usize;
+            }
+        )*
+    };
+}
+
+queue_index!(u8, u16, u32, usize);
+
+/// Convert `index` to `usize`. Trait methods cannot be
+/// called in a `const fn`, so this reads `index` as the
+/// unsigned integer type of its size.
+#[inline]
+const fn index_to_usize<Ix: QueueIndex>(index: &Ix) -> usize {
+    let p = index as *const Ix;
+    // Safety: QueueIndex is sealed and implemented only for
+    // unsigned integer types, so Ix is the unsigned integer
+    // type of its size.
+    unsafe {
+        match mem::size_of::<Ix>() {
+            1 => *(p as *const u8) as usize,
+            2 => *(p as *const u16) as usize,
+            4 => *(p as *const u32) as usize,
+            _ => *(p as *const usize),
+        }
+    }
+}
+
+/// Convert `n`, which must be at most `Ix::MAX`, to `Ix`.
+/// The queue fields are assigned directly with this rather
+/// than through a `&mut self` method, which would invalidate
+/// raw pointers into the values.
+#[inline]
+fn to_index<Ix: QueueIndex>(n: usize) -> Ix {
+    debug_assert!(n <= Ix::MAX);
+    Ix::from_usize(n)
+}
+
 /// A queue (first-in first-out) data structure of fixed
 /// capacity, using no heap storage.
-pub struct Queue<const C: usize, T> {
+///
+/// The start index and length of the queue are stored as
+/// the [QueueIndex] type `Ix`, `usize` by default.
+pub struct Queue<const C: usize, T, Ix: QueueIndex = usize> {
     values: [MaybeUninit<T>; C],
-    start: usize,
-    len: usize,
+    start: Ix,
+    len: Ix,
 }
 
-impl<const C: usize, T> Queue<C, T> {
+impl<const C: usize, T, Ix: QueueIndex> Queue<C, T, Ix> {
+    /// The capacity of this queue type (maximum number of
+    /// values that may be stored).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// type Q = Queue<4, u32>;
+    /// let flags = [false; Q::CAPACITY];
+    /// assert_eq!(4, flags.len());
+    /// ```
+    pub const CAPACITY: usize = C;
+
+    /// Make a new empty queue. This is a `const fn`, so it
+    /// can be used to initialize a `static`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// const EMPTY: Queue<4, u32> = Queue::new();
+    /// let mut q = EMPTY;
+    /// q.insert(1).unwrap();
+    /// assert_eq!(1, q.len());
+    /// ```
+    pub const fn new() -> Self {
+        const {
+            assert!(
+                C <= Ix::MAX,
+                "queue capacity is too large for its index type"
+            )
+        };
+        Self {
+            values: [const { MaybeUninit::uninit() }; C],
+            start: Ix::ZERO,
+            len: Ix::ZERO,
+        }
+    }
+
+    /// Make a queue holding the values of `iter`, in order.
+    /// An iterator yielding exactly `C` values fills the
+    /// queue.
+    ///
+    /// # Errors
+    ///
+    /// Returns [QueueError::Overflow] if the iterator yields
+    /// more than `C` values. The values taken from the
+    /// iterator, including the one that did not fit, are
+    /// dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::{Queue, QueueError};
+    /// let q: Queue<3, usize> = Queue::try_from_iter(0..3).unwrap();
+    /// assert!(q.is_full());
+    /// let r = Queue::<3, usize>::try_from_iter(0..4);
+    /// assert!(matches!(r, Err(QueueError::Overflow)));
+    /// ```
+    pub fn try_from_iter(iter: impl IntoIterator<Item = T>) -> Result<Self, QueueError> {
+        let mut q = Self::default();
+        for v in iter {
+            if q.is_full() {
+                return Err(QueueError::Overflow);
+            }
+            // Safety: The queue is not full.
+            unsafe { q.push_unchecked(v) };
+        }
+        Ok(q)
+    }
+
+    /// Make a queue holding the values of an array of length
+    /// `N`, in order. `N` must be no greater than `C`; this is
+    /// checked at compile time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let q: Queue<4, usize> = Queue::from_partial([1, 2]);
+    /// assert_eq!(q, [1, 2]);
+    /// ```
+    ///
+    /// An array longer than the queue capacity is rejected.
+    ///
+    /// ```compile_fail
+    /// # use smallqueue::Queue;
+    /// let q: Queue<2, usize> = Queue::from_partial([1, 2, 3]);
+    /// ```
+    pub fn from_partial<const N: usize>(values: [T; N]) -> Self {
+        const { assert!(N <= C, "array is longer than the queue capacity") };
+        let mut q = Self::default();
+        for v in values {
+            // Safety: There are at most C values.
+            unsafe { q.push_unchecked(v) };
+        }
+        q
+    }
+
+    /// Make a full queue whose value at each position `i`
+    /// from the front is `f(i)`, as with [core::array::from_fn].
+    /// If `f` panics, the values already made are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let q: Queue<4, usize> = Queue::from_fn(|i| i * i);
+    /// assert_eq!(q, [0, 1, 4, 9]);
+    /// ```
+    pub fn from_fn(f: impl FnMut(usize) -> T) -> Self {
+        match Self::from_fn_partial(C, f) {
+            Ok(q) => q,
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Make a queue of `n` values whose value at each
+    /// position `i` from the front is `f(i)`, as with
+    /// [Queue::from_fn].
+    ///
+    /// # Errors
+    ///
+    /// Returns [QueueError::Overflow], without calling `f`, if
+    /// `n` is greater than the queue capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::{Queue, QueueError};
+    /// let q: Queue<4, usize> = Queue::from_fn_partial(2, |i| i + 1).unwrap();
+    /// assert_eq!(q, [1, 2]);
+    /// let r = Queue::<4, usize>::from_fn_partial(5, |i| i);
+    /// assert!(matches!(r, Err(QueueError::Overflow)));
+    /// ```
+    pub fn from_fn_partial(n: usize, mut f: impl FnMut(usize) -> T) -> Result<Self, QueueError> {
+        if n > C {
+            return Err(QueueError::Overflow);
+        }
+        let mut q = Self::new();
+        for i in 0..n {
+            // Safety: At most n <= C values are pushed. The
+            // length is kept current, so a panic in f drops
+            // exactly the values already made.
+            unsafe { q.push_unchecked(f(i)) };
+        }
+        Ok(q)
+    }
+
+    /// Make a full queue with every slot holding a clone of
+    /// `value`. As with `vec![value; n]`, the last slot takes
+    /// `value` itself, so there are `C - 1` clones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let q: Queue<3, String> = Queue::filled("x".to_string());
+    /// assert!(q.is_full());
+    /// assert_eq!(q, ["x", "x", "x"]);
+    /// ```
+    pub fn filled(value: T) -> Self
+    where
+        T: Clone,
+    {
+        let mut q = Self::new();
+        if C == 0 {
+            return q;
+        }
+        for _ in 1..C {
+            // Safety: At most C - 1 values are pushed here.
+            unsafe { q.push_unchecked(value.clone()) };
+        }
+        // Safety: This is the last of the C values.
+        unsafe { q.push_unchecked(value) };
+        q
+    }
+
+    /// Make a full queue with every slot holding a copy of
+    /// `value`. This is [Queue::filled] for [Copy] values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let q: Queue<3, u8> = Queue::splat(7);
+    /// assert_eq!(q, [7, 7, 7]);
+    /// ```
+    pub fn splat(value: T) -> Self
+    where
+        T: Copy,
+    {
+        Self::from_partial([value; C])
+    }
+
+    /// Make a queue holding the first `C` values of `iter`,
+    /// in order. No further values are taken from the
+    /// iterator once the queue is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut values = 0..5;
+    /// let q: Queue<3, usize> = Queue::from_iter_truncated(&mut values);
+    /// assert_eq!(q, [0, 1, 2]);
+    /// assert_eq!(Some(3), values.next());
+    /// ```
+    pub fn from_iter_truncated(iter: impl IntoIterator<Item = T>) -> Self {
+        let mut q = Self::default();
+        for v in iter.into_iter().take(C) {
+            // Safety: At most C values are taken.
+            unsafe { q.push_unchecked(v) };
+        }
+        q
+    }
+
     /// Insert the given `value` into the queue.
     ///
     /// See the module documentation for an example.
     ///
     /// # Errors
     ///
-    /// Returns [QueueError::Overflow] if the queue is full.
-    pub fn insert(&mut self, value: T) -> Result<(), QueueError> {
-        let cap = self.values.len();
-        if self.len + 1 > cap {
+    /// Returns [InsertError] holding `value` if the queue is
+    /// full.
+    ///
+    /// ```
+    /// # use smallqueue::{InsertError, Queue};
+    /// let mut q: Queue<1, String> = Queue::default();
+    /// q.insert("a".to_string()).unwrap();
+    /// let err = q.insert("b".to_string());
+    /// assert_eq!(Err(InsertError("b".to_string())), err);
+    /// ```
+    #[must_use = "if the queue is full the value is returned in the error"]
+    #[inline]
+    pub fn insert(&mut self, value: T) -> Result<(), InsertError<T>> {
+        if self.is_full() {
+            return Err(InsertError(value));
+        }
+        // Safety: The queue is not full.
+        unsafe { self.push_unchecked(value) };
+        Ok(())
+    }
+
+    /// Insert the value returned by `f` into the queue. `f` is
+    /// called only if there is room for its result, and its
+    /// result is written directly into place.
+    ///
+    /// # Errors
+    ///
+    /// Returns [QueueError::Overflow] without calling `f` if
+    /// the queue is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<1, String> = Queue::default();
+    /// q.insert_with(|| "a".to_string()).unwrap();
+    /// assert!(q.insert_with(|| unreachable!()).is_err());
+    /// ```
+    pub fn insert_with(&mut self, f: impl FnOnce() -> T) -> Result<(), QueueError> {
+        if self.is_full() {
             return Err(QueueError::Overflow);
         }
-        // Safety: We are only writing to a location at an index that
-        // is bounds-checked.
+        let slot = self.slot(self.len());
+        self.values[slot].write(f());
+        self.len = to_index(self.len() + 1);
+        Ok(())
+    }
+
+    /// Insert the given `value` at the front of the queue,
+    /// so that it will be the next value extracted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [InsertError] holding `value` if the queue is
+    /// full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// q.insert(17).unwrap();
+    /// q.push_front(16).unwrap();
+    /// assert_eq!(Some(16), q.extract());
+    /// assert_eq!(Some(17), q.extract());
+    /// ```
+    #[must_use = "if the queue is full the value is returned in the error"]
+    pub fn push_front(&mut self, value: T) -> Result<(), InsertError<T>> {
+        if self.is_full() {
+            return Err(InsertError(value));
+        }
+        self.start = to_index(Self::wrap(self.start() + C - 1));
+        // Safety: The queue is not full, so the slot before the
+        // first value is unoccupied.
         unsafe {
-            self.values[(self.start + self.len) % cap].as_mut_ptr().write(value);
+            self.values[self.start()].as_mut_ptr().write(value);
         }
-        self.len += 1;
+        self.len = to_index(self.len() + 1);
         Ok(())
     }
 
+    /// Insert the given `value` into the queue. If the queue
+    /// is full, its first value is first removed to make
+    /// room, and returned as `Some`; otherwise `None` is
+    /// returned. (A queue of capacity 0 returns `value`
+    /// itself.)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// for i in 0..5 {
+    ///     let evicted = q.force_insert(i);
+    ///     assert_eq!(i.checked_sub(3), evicted);
+    /// }
+    /// for i in 2..5 {
+    ///     assert_eq!(Some(i), q.extract());
+    /// }
+    /// ```
+    pub fn force_insert(&mut self, value: T) -> Option<T> {
+        let evicted = if self.is_full() {
+            match self.extract() {
+                Some(v) => Some(v),
+                // A zero-capacity queue can only evict the new
+                // value.
+                None => return Some(value),
+            }
+        } else {
+            None
+        };
+        // Safety: The queue is not full.
+        unsafe { self.push_unchecked(value) };
+        evicted
+    }
+
     /// Returns `Some` first value in the queue if one
     /// exists, and `None` otherwise.
     ///
     /// See the module documentation for an example.
+    #[inline]
     pub fn extract(&mut self) -> Option<T> {
-        if self.len == 0 {
+        if self.is_empty() {
             return None;
         }
-        let cap = self.values.len();
-        // Safety: We are retrieving a value that was previously
-        // inserted, as evidenced by the values of start and len.
+        // Safety: start is always less than the capacity, so
+        // the slot is in bounds. We are retrieving a value that
+        // was previously inserted, as evidenced by the values
+        // of start and len.
         let val = unsafe {
-            ptr::read(self.values[self.start].as_ptr())
+            self.values.get_unchecked(self.start()).assume_init_read()
         };
-        self.start = (self.start + 1) % cap;
-        self.len -= 1;
+        // start < C, so start + 1 < 2 * C as wrap requires.
+        self.start = to_index(Self::wrap(self.start() + 1));
+        self.len = to_index(self.len() - 1);
         Some(val)
     }
 
-    /// Returns the capacity of this queue (maximum number
-    /// of values that may be stored) as defined at
-    /// compile-time.
+    /// Replace the first value in the queue with `value`,
+    /// returning the previous first value as `Ok`. If the
+    /// queue is empty, `value` is not inserted and is
+    /// returned as `Err`. The queue length never changes.
     ///
     /// # Examples
     ///
     /// ```
     /// # use smallqueue::Queue;
     /// let mut q: Queue<3, usize> = Queue::default();
-    /// assert_eq!(3, q.capacity());
+    /// assert_eq!(Err(16), q.replace_front(16));
+    /// q.insert(17).unwrap();
+    /// assert_eq!(Ok(17), q.replace_front(18));
+    /// assert_eq!(Some(18), q.extract());
     /// ```
-    pub const fn capacity(&self) -> usize {
-        self.values.len()
+    pub fn replace_front(&mut self, value: T) -> Result<T, T> {
+        match self.front_mut() {
+            Some(front) => Ok(mem::replace(front, value)),
+            None => Err(value),
+        }
     }
 
-    /// Returns the number of values currently stored in the
-    /// queue.
+    /// Returns `Some` first value in the queue, removing it,
+    /// if the queue is nonempty and `pred` returns `true` for
+    /// that value. Returns `None`, leaving the queue
+    /// unchanged apart from any modification made by `pred`,
+    /// if the queue is empty or `pred` returns `false`; use
+    /// [Queue::is_empty] to tell these cases apart.
     ///
     /// # Examples
     ///
     /// ```
     /// # use smallqueue::Queue;
     /// let mut q: Queue<3, usize> = Queue::default();
-    /// q.insert(17);
-    /// assert_eq!(1, q.len());
+    /// q.insert(17).unwrap();
+    /// assert_eq!(None, q.pop_if(|v| *v > 17));
+    /// assert_eq!(Some(17), q.pop_if(|v| *v == 17));
     /// ```
-    pub fn len(&self) -> usize {
-        self.len
+    pub fn pop_if(&mut self, pred: impl FnOnce(&mut T) -> bool) -> Option<T> {
+        if pred(self.front_mut()?) {
+            self.extract()
+        } else {
+            None
+        }
     }
 
-    /// Returns `false` if the queue contains values, but
-    /// `true` otherwise.
+    /// Returns `Some` last value in the queue, removing it,
+    /// if one exists, and `None` otherwise.
     ///
     /// # Examples
     ///
     /// ```
     /// # use smallqueue::Queue;
     /// let mut q: Queue<3, usize> = Queue::default();
-    /// assert!(q.is_empty());
-    /// q.insert(17);
-    /// assert!(!q.is_empty());
+    /// q.insert(17).unwrap();
+    /// q.insert(18).unwrap();
+    /// assert_eq!(Some(18), q.pop_back());
+    /// assert_eq!(Some(17), q.extract());
+    /// assert_eq!(None, q.pop_back());
     /// ```
-    pub fn is_empty(&self) -> bool {
-        self.len == 0
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.len = to_index(self.len() - 1);
+        // Safety: We are retrieving the last value that was
+        // previously inserted, which is no longer part of the
+        // queue.
+        Some(unsafe { ptr::read(self.values[self.slot(self.len())].as_ptr()) })
     }
-}
 
-impl<const C: usize, T> Default for Queue<C, T> {
-    fn default() -> Self {
-        Self {
-            values: array::from_fn(|_| MaybeUninit::uninit()),
-            start: 0,
-            len: 0,
+    /// Returns `Some` reference to the first value in the
+    /// queue if one exists, and `None` otherwise. The queue
+    /// is not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// assert_eq!(None, q.front());
+    /// q.insert(17).unwrap();
+    /// q.insert(18).unwrap();
+    /// assert_eq!(Some(&17), q.front());
+    /// assert_eq!(2, q.len());
+    /// ```
+    pub fn front(&self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
         }
+        // Safety: The value at start was previously inserted,
+        // since the queue is nonempty.
+        Some(unsafe { &*self.values[self.start()].as_ptr() })
     }
-}
 
-impl<const C: usize, T> Drop for Queue<C, T> {
-    fn drop(&mut self) {
-        let cap = self.values.len();
-        let start = self.start;
-        for i in 0..self.len {
-            // Safety: All of the dropped values are initialized.
-            unsafe {
-                ptr::drop_in_place(self.values[(start + i) % cap].as_mut_ptr());
-            }
+    /// Returns `Some` mutable reference to the first value
+    /// in the queue if one exists, and `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// q.insert(17).unwrap();
+    /// *q.front_mut().unwrap() += 1;
+    /// assert_eq!(Some(18), q.extract());
+    /// ```
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        if self.is_empty() {
+            return None;
         }
+        // Safety: The value at start was previously inserted,
+        // since the queue is nonempty. The returned reference
+        // borrows the queue mutably, so no other reference to
+        // the slot can exist while it is live.
+        Some(unsafe { &mut *self.values[self.start()].as_mut_ptr() })
     }
-}
-
-#[test]
-fn test_queue() {
-    #[derive(Debug, PartialEq, Eq)]
-    struct S(usize);
 
-    let mut q: Queue<3, S> = Queue::default();
-    assert!(q.is_empty());
-    for i in 0..3 {
-        q.insert(S(i)).unwrap();
+    /// Returns `Some` reference to the last value in the
+    /// queue if one exists, and `None` otherwise. The queue
+    /// is not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// assert_eq!(None, q.back());
+    /// q.insert(17).unwrap();
+    /// q.insert(18).unwrap();
+    /// assert_eq!(Some(&18), q.back());
+    /// assert_eq!(2, q.len());
+    /// ```
+    pub fn back(&self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+        // Safety: The value at the end of the queue was
+        // previously inserted, since the queue is nonempty.
+        Some(unsafe { &*self.values[self.slot(self.len() - 1)].as_ptr() })
     }
-    assert_eq!(3, q.len());
-    let ovf = q.insert(S(3));
-    assert!(matches!(ovf, Err(QueueError::Overflow)));
-    assert!(matches!(q.extract(), Some(S(0))));
-    q.insert(S(3)).unwrap();
-    assert_eq!(3, q.len());
-    for i in 1..=3 {
-        assert_eq!(Some(S(i)), q.extract());
+
+    /// Returns `Some` mutable reference to the last value in
+    /// the queue if one exists, and `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, String> = Queue::default();
+    /// q.insert("ab".to_string()).unwrap();
+    /// q.back_mut().unwrap().push('c');
+    /// assert_eq!("abc", q.extract().unwrap());
+    /// ```
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        if self.is_empty() {
+            return None;
+        }
+        // Safety: The value at the end of the queue was
+        // previously inserted, since the queue is nonempty. The
+        // returned reference borrows the queue mutably, so no
+        // other reference to the slot can exist while it is
+        // live.
+        Some(unsafe { &mut *self.values[self.slot(self.len() - 1)].as_mut_ptr() })
     }
-    assert!(matches!(q.extract(), None));
-    assert!(q.is_empty());
+
+    /// Returns `Some` reference to the value at logical
+    /// position `index` in the queue, counting from the
+    /// front (0 is the oldest value), and `None` if there is
+    /// no such value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// q.insert(17).unwrap();
+    /// q.insert(18).unwrap();
+    /// assert_eq!(q.front(), q.get(0));
+    /// assert_eq!(Some(&18), q.get(1));
+    /// assert_eq!(None, q.get(2));
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+        // Safety: Logical positions less than len hold
+        // previously inserted values.
+        Some(unsafe { &*self.values[self.slot(index)].as_ptr() })
+    }
+
+    /// Returns `Some` mutable reference to the value at
+    /// logical position `index` in the queue, counting from
+    /// the front, and `None` if there is no such value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// q.insert(17).unwrap();
+    /// q.insert(18).unwrap();
+    /// *q.get_mut(1).unwrap() = 19;
+    /// assert_eq!(Some(17), q.extract());
+    /// assert_eq!(Some(19), q.extract());
+    /// ```
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len() {
+            return None;
+        }
+        let slot = self.slot(index);
+        // Safety: Logical positions less than len hold
+        // previously inserted values. The returned reference
+        // borrows the queue mutably.
+        Some(unsafe { &mut *self.values[slot].as_mut_ptr() })
+    }
+
+    /// Returns `true` if some value in the queue is equal to
+    /// `value`, and `false` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// q.insert(17).unwrap();
+    /// assert!(q.contains(&17));
+    /// assert!(!q.contains(&18));
+    /// ```
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|v| v == value)
+    }
+
+    /// Returns a pair of slices which together contain the
+    /// values of the queue in order. The second slice is
+    /// empty unless the contents wrap around the end of the
+    /// backing array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// for i in 0..3 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// q.extract().unwrap();
+    /// q.insert(3).unwrap();
+    /// assert_eq!((&[1, 2][..], &[3][..]), q.as_slices());
+    /// ```
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (head, tail) = self.occupied();
+        let values = self.values.as_ptr() as *const T;
+        // Safety: The occupied ranges are in bounds and hold
+        // previously inserted values.
+        unsafe {
+            (
+                slice::from_raw_parts(values.add(head.start), head.len()),
+                slice::from_raw_parts(values.add(tail.start), tail.len()),
+            )
+        }
+    }
+
+    /// Returns a pair of mutable slices which together
+    /// contain the values of the queue in order. The second
+    /// slice is empty unless the contents wrap around the end
+    /// of the backing array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// for i in 0..3 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// q.extract().unwrap();
+    /// q.insert(3).unwrap();
+    /// let (head, tail) = q.as_mut_slices();
+    /// head[0] = 5;
+    /// tail[0] = 6;
+    /// assert_eq!((&[5, 2][..], &[6][..]), q.as_slices());
+    /// ```
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (head, tail) = self.occupied();
+        let values = self.values.as_mut_ptr() as *mut T;
+        // Safety: The occupied ranges are in bounds, hold
+        // previously inserted values, and are disjoint.
+        unsafe {
+            (
+                slice::from_raw_parts_mut(values.add(head.start), head.len()),
+                slice::from_raw_parts_mut(values.add(tail.start), tail.len()),
+            )
+        }
+    }
+
+    /// Returns `true` if the values of the queue occupy a
+    /// single contiguous run of the backing array, and
+    /// `false` if they wrap around its end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<2, usize> = Queue::default();
+    /// q.insert(17).unwrap();
+    /// q.insert(18).unwrap();
+    /// assert!(q.is_contiguous());
+    /// q.extract().unwrap();
+    /// q.insert(19).unwrap();
+    /// assert!(!q.is_contiguous());
+    /// ```
+    pub const fn is_contiguous(&self) -> bool {
+        self.start() + self.len() <= self.values.len()
+    }
+
+    /// Returns `Some` slice containing the values of the
+    /// queue in order if they are contiguous (see
+    /// [Queue::is_contiguous]), and `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<2, usize> = Queue::default();
+    /// q.insert(17).unwrap();
+    /// q.insert(18).unwrap();
+    /// assert_eq!(Some(&[17, 18][..]), q.as_contiguous());
+    /// ```
+    pub fn as_contiguous(&self) -> Option<&[T]> {
+        if !self.is_contiguous() {
+            return None;
+        }
+        Some(self.as_slices().0)
+    }
+
+    /// Copy values from the front of the queue into `dst`
+    /// without removing them, stopping when either `dst` is
+    /// full or the queue is exhausted. Returns the number of
+    /// values copied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, u8> = Queue::default();
+    /// for i in 1..=3 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// let mut buf = [0; 2];
+    /// assert_eq!(2, q.copy_to_slice(&mut buf));
+    /// assert_eq!([1, 2], buf);
+    /// assert_eq!(3, q.len());
+    /// ```
+    pub fn copy_to_slice(&self, dst: &mut [T]) -> usize
+    where
+        T: Copy,
+    {
+        let (head, tail) = self.as_slices();
+        let n = dst.len().min(self.len());
+        let nhead = n.min(head.len());
+        dst[..nhead].copy_from_slice(&head[..nhead]);
+        dst[nhead..n].copy_from_slice(&tail[..n - nhead]);
+        n
+    }
+
+    /// Rearranges the internal storage of the queue so that
+    /// its values are contiguous, and returns a mutable slice
+    /// containing them in order. If the values are already
+    /// contiguous they are not moved; otherwise the queue is
+    /// rotated so that it starts at the beginning of the
+    /// backing array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// for i in [3, 2, 1] {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// q.extract().unwrap();
+    /// q.insert(0).unwrap();
+    /// q.make_contiguous().sort();
+    /// assert_eq!((&[0, 1, 2][..], &[][..]), q.as_slices());
+    /// ```
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if !self.is_contiguous() {
+            // Rotating the uninitialized wrappers moves the
+            // values without dropping or duplicating any.
+            let start = self.start();
+            self.values.rotate_left(start);
+            self.start = to_index(0);
+        }
+        self.as_mut_slices().0
+    }
+
+    /// Move the values of a full queue into an array, from
+    /// front to back. A queue that is not full is handed back
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::from([0, 1, 2]);
+    /// q.extract().unwrap();
+    /// let mut q = q.into_array().unwrap_err();
+    /// assert_eq!(q, [1, 2]);
+    /// q.insert(3).unwrap();
+    /// assert_eq!([1, 2, 3], q.into_array().unwrap());
+    /// ```
+    pub fn into_array(mut self) -> Result<[T; C], Self> {
+        if !self.is_full() {
+            return Err(self);
+        }
+        match self.extract_array() {
+            Some(values) => Ok(values),
+            None => unreachable!(),
+        }
+    }
+
+    /// Move the values of this queue, in order, into a new
+    /// queue of capacity `D`, which must be at least `C`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let q: Queue<2, usize> = Queue::from([1, 2]);
+    /// let mut q: Queue<4, usize> = q.grow();
+    /// q.insert(3).unwrap();
+    /// assert_eq!(q, [1, 2, 3]);
+    /// ```
+    ///
+    /// A smaller capacity fails to compile.
+    ///
+    /// ```compile_fail
+    /// # use smallqueue::Queue;
+    /// let q: Queue<2, usize> = Queue::from([1, 2]);
+    /// let q: Queue<1, usize> = q.grow();
+    /// ```
+    pub fn grow<const D: usize>(self) -> Queue<D, T, Ix> {
+        const { assert!(D >= C, "grown capacity is less than the queue capacity") };
+        // Safety: There are at most C <= D values.
+        unsafe { self.move_into() }
+    }
+
+    /// Move the values of this queue, in order, into a new
+    /// queue of capacity `D`. A queue holding more than `D`
+    /// values is handed back unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let q: Queue<4, usize> = Queue::from([1, 2, 3, 4]);
+    /// let mut q = q.shrink::<2>().unwrap_err();
+    /// q.drop_front(2);
+    /// let q: Queue<2, usize> = q.shrink().unwrap();
+    /// assert!(q.is_full());
+    /// assert_eq!(q, [3, 4]);
+    /// ```
+    pub fn shrink<const D: usize>(self) -> Result<Queue<D, T, Ix>, Self> {
+        if self.len() > D {
+            return Err(self);
+        }
+        // Safety: There are at most D values.
+        Ok(unsafe { self.move_into() })
+    }
+
+    /// Move the values of this queue followed by those of
+    /// `other`, in order, into a new queue whose capacity `E`
+    /// is the sum of theirs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let header: Queue<2, u8> = Queue::from([0xa5, 3]);
+    /// let payload: Queue<4, u8> = Queue::from_partial([1, 2, 3]);
+    /// let frame: Queue<6, u8> = header.concat(payload);
+    /// assert_eq!(frame, [0xa5, 3, 1, 2, 3]);
+    /// ```
+    ///
+    /// A capacity other than the sum fails to compile.
+    ///
+    /// ```compile_fail
+    /// # use smallqueue::Queue;
+    /// let a: Queue<2, u8> = Queue::new();
+    /// let b: Queue<2, u8> = Queue::new();
+    /// let c: Queue<5, u8> = a.concat(b);
+    /// ```
+    pub fn concat<const D: usize, const E: usize, Jx: QueueIndex>(
+        self,
+        mut other: Queue<D, T, Jx>,
+    ) -> Queue<E, T, Ix> {
+        const {
+            assert!(
+                E == C + D,
+                "capacity is not the sum of the queue capacities"
+            )
+        };
+        // Safety: There are at most C <= E values.
+        let mut q = unsafe { self.move_into::<E>() };
+        let n = other.len();
+        // Safety: q starts at slot 0 and has room for the n
+        // values of other after its own, since C + D = E.
+        unsafe {
+            other.take_front((q.values.as_mut_ptr() as *mut T).add(q.len()), n);
+        }
+        q.len = to_index(q.len() + n);
+        q
+    }
+
+    /// Returns a pair of mutable slices which together
+    /// contain the unoccupied slots of the queue, in the order
+    /// in which they would be filled by [Queue::insert]. The
+    /// second slice is empty unless the free space wraps
+    /// around the end of the backing array.
+    ///
+    /// Values written here become part of the queue only
+    /// once [Queue::commit] is called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, u8> = Queue::default();
+    /// q.insert(1).unwrap();
+    /// let (head, _) = q.spare_capacity_mut();
+    /// head[0].write(2);
+    /// head[1].write(3);
+    /// // Safety: Two values were written at the tail.
+    /// unsafe { q.commit(2) };
+    /// assert_eq!(3, q.len());
+    /// assert_eq!(Some(&3), q.back());
+    /// ```
+    pub fn spare_capacity_mut(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        let cap = self.values.len();
+        let start = self.start();
+        let end = start + self.len();
+        if end < cap {
+            let (wrapped, tail) = self.values.split_at_mut(end);
+            (tail, &mut wrapped[..start])
+        } else {
+            (&mut self.values[end - cap..start], &mut [])
+        }
+    }
+
+    /// Add the `n` values following the current end of the
+    /// queue to the queue, as if they had been inserted.
+    /// This is intended for use after writing values through
+    /// [Queue::spare_capacity_mut].
+    ///
+    /// # Safety
+    ///
+    /// `n` must be no greater than
+    /// [Queue::remaining_capacity], and the first `n` slots
+    /// returned by [Queue::spare_capacity_mut] (taking the
+    /// first slice, then the second) must have been
+    /// initialized.
+    pub unsafe fn commit(&mut self, n: usize) {
+        debug_assert!(n <= self.remaining_capacity());
+        self.len = to_index(self.len() + n);
+    }
+
+    /// Insert the given `value` into the queue at logical
+    /// position `index`, so that the values previously at
+    /// `index` and after follow it. The values on whichever
+    /// side of `index` are fewer are moved to make room.
+    ///
+    /// # Errors
+    ///
+    /// Returns [InsertError] holding `value` if the queue is
+    /// full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the queue length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// q.insert(0).unwrap();
+    /// q.insert(2).unwrap();
+    /// q.insert_at(1, 1).unwrap();
+    /// for i in 0..3 {
+    ///     assert_eq!(Some(i), q.extract());
+    /// }
+    /// ```
+    #[must_use = "if the queue is full the value is returned in the error"]
+    pub fn insert_at(&mut self, index: usize, value: T) -> Result<(), InsertError<T>> {
+        if index > self.len() {
+            panic!(
+                "insertion index {index} out of bounds for queue of length {}",
+                self.len()
+            );
+        }
+        if self.is_full() {
+            return Err(InsertError(value));
+        }
+        if index < self.len() - index {
+            self.start = to_index(Self::wrap(self.start() + C - 1));
+            for i in 0..index {
+                self.shift(i + 1, i);
+            }
+        } else {
+            for i in (index..self.len()).rev() {
+                self.shift(i, i + 1);
+            }
+        }
+        let slot = self.slot(index);
+        // Safety: The slot at index has been vacated.
+        unsafe {
+            self.values[slot].as_mut_ptr().write(value);
+        }
+        self.len = to_index(self.len() + 1);
+        Ok(())
+    }
+
+    /// Swap the values at logical positions `i` and `j`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is not less than the queue
+    /// length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// for i in 0..3 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// q.swap(0, 2);
+    /// assert_eq!(Some(2), q.extract());
+    /// assert_eq!(Some(1), q.extract());
+    /// assert_eq!(Some(0), q.extract());
+    /// ```
+    pub fn swap(&mut self, i: usize, j: usize) {
+        let len = self.len();
+        for index in [i, j] {
+            if index >= len {
+                panic!("index out of bounds: the len is {len} but the index is {index}");
+            }
+        }
+        let values = self.values.as_mut_ptr();
+        // Safety: Both slots are in bounds and hold previously
+        // inserted values. ptr::swap allows them to be the
+        // same slot.
+        unsafe { ptr::swap(values.add(self.slot(i)), values.add(self.slot(j))) };
+    }
+
+    /// Reverse the order of the values in the queue in
+    /// place, so that the back value becomes the front.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// q.copy_from_slice(&[1, 2, 3]).unwrap();
+    /// q.reverse();
+    /// assert_eq!(vec![3, 2, 1], q.iter().copied().collect::<Vec<_>>());
+    /// ```
+    pub fn reverse(&mut self) {
+        let values = self.values.as_mut_ptr();
+        for i in 0..self.len() / 2 {
+            let j = self.len() - 1 - i;
+            // Safety: Both slots are in bounds, distinct, and
+            // hold previously inserted values.
+            unsafe {
+                ptr::swap_nonoverlapping(values.add(self.slot(i)), values.add(self.slot(j)), 1);
+            }
+        }
+    }
+
+    /// Rotate the queue `n` places to the left, so that the
+    /// value at logical position `n` becomes the first value
+    /// and the first `n` values move to the back in order.
+    /// When the queue is full this just adjusts the start
+    /// index; otherwise `min(n, len - n)` values are moved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than the queue length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, usize> = Queue::default();
+    /// for i in 0..3 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// q.rotate_left(1);
+    /// assert_eq!(vec![1, 2, 0], q.iter().copied().collect::<Vec<_>>());
+    /// ```
+    pub fn rotate_left(&mut self, n: usize) {
+        if n > self.len() {
+            panic!(
+                "rotation {n} out of bounds for queue of length {}",
+                self.len()
+            );
+        }
+        if n == 0 || n == self.len() {
+            return;
+        }
+        if self.is_full() {
+            self.start = to_index(self.slot(n));
+        } else if n <= self.len() - n {
+            self.rotate_forward(n);
+        } else {
+            self.rotate_backward(self.len() - n);
+        }
+    }
+
+    /// Rotate the queue `n` places to the right, so that the
+    /// last `n` values move to the front in order. When the
+    /// queue is full this just adjusts the start index;
+    /// otherwise `min(n, len - n)` values are moved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than the queue length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, usize> = Queue::default();
+    /// for i in 0..3 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// q.rotate_right(1);
+    /// assert_eq!(vec![2, 0, 1], q.iter().copied().collect::<Vec<_>>());
+    /// ```
+    pub fn rotate_right(&mut self, n: usize) {
+        if n > self.len() {
+            panic!(
+                "rotation {n} out of bounds for queue of length {}",
+                self.len()
+            );
+        }
+        if n == 0 || n == self.len() {
+            return;
+        }
+        if self.is_full() {
+            self.start = to_index(Self::wrap(self.start() + C - n));
+        } else if n <= self.len() - n {
+            self.rotate_backward(n);
+        } else {
+            self.rotate_forward(self.len() - n);
+        }
+    }
+
+    /// Returns `Some` value at logical position `index`,
+    /// removing it from the queue, if one exists, and `None`
+    /// otherwise. The values on whichever side of `index` are
+    /// fewer are moved to close the gap; the order of the
+    /// remaining values is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// for i in 0..3 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// assert_eq!(Some(1), q.remove(1));
+    /// assert_eq!(None, q.remove(2));
+    /// assert_eq!(Some(0), q.extract());
+    /// assert_eq!(Some(2), q.extract());
+    /// ```
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+        // Safety: The value at index was previously inserted.
+        // Its slot is overwritten or left outside the queue
+        // below.
+        let value = unsafe { ptr::read(self.values[self.slot(index)].as_ptr()) };
+        if index < self.len() - index - 1 {
+            for i in (0..index).rev() {
+                self.shift(i, i + 1);
+            }
+            self.start = to_index(self.slot(1));
+        } else {
+            for i in index + 1..self.len() {
+                self.shift(i, i - 1);
+            }
+        }
+        self.len = to_index(self.len() - 1);
+        Some(value)
+    }
+
+    /// Returns `Some` value at logical position `index`,
+    /// removing it from the queue, if one exists, and `None`
+    /// otherwise. The first value of the queue is moved into
+    /// the vacated position, so this takes constant time but
+    /// changes the order of the values before `index`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, usize> = Queue::default();
+    /// for i in 0..4 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// assert_eq!(Some(2), q.swap_remove_front(2));
+    /// assert_eq!(Some(1), q.extract());
+    /// assert_eq!(Some(0), q.extract());
+    /// assert_eq!(Some(3), q.extract());
+    /// ```
+    pub fn swap_remove_front(&mut self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+        self.swap(0, index);
+        self.extract()
+    }
+
+    /// Returns `Some` value at logical position `index`,
+    /// removing it from the queue, if one exists, and `None`
+    /// otherwise. The last value of the queue is moved into
+    /// the vacated position, so this takes constant time but
+    /// changes the order of the values after `index`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, usize> = Queue::default();
+    /// for i in 0..4 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// assert_eq!(Some(1), q.swap_remove_back(1));
+    /// assert_eq!(Some(0), q.extract());
+    /// assert_eq!(Some(3), q.extract());
+    /// assert_eq!(Some(2), q.extract());
+    /// ```
+    pub fn swap_remove_back(&mut self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+        self.swap(index, self.len() - 1);
+        self.pop_back()
+    }
+
+    /// Move all the values of `other` to the back of this
+    /// queue, in order, leaving `other` empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns [QueueError::Overflow] if the values of
+    /// `other` will not all fit. In this case neither queue
+    /// is changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, usize> = Queue::default();
+    /// let mut r: Queue<2, usize> = Queue::default();
+    /// q.insert(0).unwrap();
+    /// r.insert(1).unwrap();
+    /// r.insert(2).unwrap();
+    /// q.append(&mut r).unwrap();
+    /// assert!(r.is_empty());
+    /// assert_eq!(vec![0, 1, 2], q.iter().copied().collect::<Vec<_>>());
+    /// ```
+    pub fn append<const D: usize, Jx: QueueIndex>(
+        &mut self,
+        other: &mut Queue<D, T, Jx>,
+    ) -> Result<(), QueueError> {
+        if other.len() > self.remaining_capacity() {
+            return Err(QueueError::Overflow);
+        }
+        let n = other.len();
+        other.len = to_index(0);
+        for i in 0..n {
+            let src = other.slot(i);
+            // Safety: The source slot holds a value of other,
+            // which no longer owns it, and there is room for
+            // all of the values.
+            unsafe {
+                let value = ptr::read(other.values[src].as_ptr());
+                self.push_unchecked(value);
+            }
+        }
+        other.start = to_index(0);
+        Ok(())
+    }
+
+    /// Split the first `n` values off into a new queue,
+    /// returned in order. This queue keeps the remaining
+    /// values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than the queue length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, usize> = Queue::default();
+    /// for i in 0..4 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// let r = q.split_to(1);
+    /// assert_eq!(vec![0], r.iter().copied().collect::<Vec<_>>());
+    /// assert_eq!(vec![1, 2, 3], q.iter().copied().collect::<Vec<_>>());
+    /// ```
+    pub fn split_to(&mut self, n: usize) -> Queue<C, T, Ix> {
+        if n > self.len() {
+            panic!("split {n} out of bounds for queue of length {}", self.len());
+        }
+        let mut result = Self::default();
+        for i in 0..n {
+            let src = self.slot(i);
+            // Safety: The source slot holds a value which is
+            // removed from this queue below; the destination
+            // slot is unoccupied.
+            unsafe {
+                let value = ptr::read(self.values[src].as_ptr());
+                result.values[i].as_mut_ptr().write(value);
+            }
+        }
+        result.len = to_index(n);
+        if n > 0 {
+            self.start = to_index(self.slot(n));
+            self.len = to_index(self.len() - n);
+        }
+        result
+    }
+
+    /// Split the last `n` values off into a new queue,
+    /// returned in order. This queue keeps the remaining
+    /// values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than the queue length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, usize> = Queue::default();
+    /// for i in 0..4 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// let r = q.split_off_back(1);
+    /// assert_eq!(vec![3], r.iter().copied().collect::<Vec<_>>());
+    /// assert_eq!(vec![0, 1, 2], q.iter().copied().collect::<Vec<_>>());
+    /// ```
+    pub fn split_off_back(&mut self, n: usize) -> Queue<C, T, Ix> {
+        if n > self.len() {
+            panic!("split {n} out of bounds for queue of length {}", self.len());
+        }
+        let mut result = Self::default();
+        let keep = self.len() - n;
+        for i in 0..n {
+            let src = self.slot(keep + i);
+            // Safety: The source slot holds a value which is
+            // removed from this queue below; the destination
+            // slot is unoccupied.
+            unsafe {
+                let value = ptr::read(self.values[src].as_ptr());
+                result.values[i].as_mut_ptr().write(value);
+            }
+        }
+        result.len = to_index(n);
+        self.len = to_index(keep);
+        result
+    }
+
+    /// Insert clones of the values of `src` into the back of
+    /// the queue, in order.
+    ///
+    /// If cloning a value panics, the values cloned so far
+    /// remain in the queue.
+    ///
+    /// # Errors
+    ///
+    /// Returns [QueueError::Overflow] if the values of `src`
+    /// will not all fit. In this case nothing is cloned and
+    /// the queue is not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, usize> = Queue::default();
+    /// q.extend_from_slice(&[1, 2, 3]).unwrap();
+    /// assert!(q.extend_from_slice(&[4, 5]).is_err());
+    /// q.extend_from_slice(&[4]).unwrap();
+    /// assert_eq!(vec![1, 2, 3, 4], q.iter().copied().collect::<Vec<_>>());
+    /// ```
+    pub fn extend_from_slice(&mut self, src: &[T]) -> Result<(), QueueError>
+    where
+        T: Clone,
+    {
+        if src.len() > self.remaining_capacity() {
+            return Err(QueueError::Overflow);
+        }
+        for value in src {
+            // Safety: There is room for all of the values.
+            unsafe { self.push_unchecked(value.clone()) };
+        }
+        Ok(())
+    }
+
+    /// Copy the values of `src` into the back of the queue,
+    /// in order. This is a faster equivalent of
+    /// [Queue::extend_from_slice] for [Copy] values.
+    ///
+    /// # Errors
+    ///
+    /// Returns [QueueError::Overflow] if the values of `src`
+    /// will not all fit. In this case the queue is not
+    /// changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, u8> = Queue::default();
+    /// q.copy_from_slice(b"abc").unwrap();
+    /// assert!(q.copy_from_slice(b"de").is_err());
+    /// q.copy_from_slice(b"d").unwrap();
+    /// assert_eq!(b"abcd".to_vec(), q.iter().copied().collect::<Vec<_>>());
+    /// ```
+    pub fn copy_from_slice(&mut self, src: &[T]) -> Result<(), QueueError>
+    where
+        T: Copy,
+    {
+        if src.len() > self.remaining_capacity() {
+            return Err(QueueError::Overflow);
+        }
+        // Safety: There is room for all of src, which is
+        // borrowed separately. Its values are Copy, so src
+        // keeps them too.
+        unsafe { self.put_back(src.as_ptr(), src.len()) };
+        Ok(())
+    }
+
+    /// Insert values taken from `iter` into the back of the
+    /// queue until either the queue is full or `iter` is
+    /// exhausted. Returns the number of values inserted. No
+    /// value is taken from `iter` unless there is room for
+    /// it, so the remaining values can still be obtained from
+    /// `iter` afterward.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// let mut samples = 0..5;
+    /// assert_eq!(3, q.insert_many(samples.by_ref()));
+    /// assert_eq!(Some(3), samples.next());
+    /// ```
+    pub fn insert_many(&mut self, iter: impl IntoIterator<Item = T>) -> usize {
+        let mut iter = iter.into_iter();
+        let mut n = 0;
+        while !self.is_full() {
+            let Some(value) = iter.next() else {
+                break;
+            };
+            // Safety: The queue is not full.
+            unsafe { self.push_unchecked(value) };
+            n += 1;
+        }
+        n
+    }
+
+    /// Insert the values of `iter` into the back of the
+    /// queue, in order.
+    ///
+    /// # Errors
+    ///
+    /// If the queue fills before `iter` is exhausted, returns
+    /// a [TryExtendError] holding the one value taken from
+    /// `iter` that did not fit, along with `iter` itself,
+    /// from which the remaining values can still be taken.
+    /// An iterator that ends exactly when the queue fills
+    /// succeeds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// let err = q.try_extend(0..5).unwrap_err();
+    /// assert_eq!(3, err.value);
+    /// assert_eq!(Some(4), { err.iter }.next());
+    /// assert!(q.is_full());
+    /// ```
+    pub fn try_extend<I: IntoIterator<Item = T>>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), TryExtendError<I::IntoIter>> {
+        let mut iter = iter.into_iter();
+        for value in iter.by_ref() {
+            if self.is_full() {
+                return Err(TryExtendError { value, iter });
+            }
+            // Safety: The queue is not full.
+            unsafe { self.push_unchecked(value) };
+        }
+        Ok(())
+    }
+
+    /// Move values from the front of the queue into `dst`,
+    /// oldest first, stopping when either `dst` is full or the
+    /// queue is exhausted. Returns the number of values
+    /// moved. The values previously in the affected part of
+    /// `dst` are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, u8> = Queue::default();
+    /// q.copy_from_slice(b"abc").unwrap();
+    /// let mut buf = [0; 2];
+    /// assert_eq!(2, q.extract_into(&mut buf));
+    /// assert_eq!(*b"ab", buf);
+    /// assert_eq!(Some(b'c'), q.extract());
+    /// ```
+    pub fn extract_into(&mut self, dst: &mut [T]) -> usize {
+        let n = dst.len().min(self.len());
+        if n == 0 {
+            return 0;
+        }
+        let dst = dst.as_mut_ptr();
+        if !mem::needs_drop::<T>() {
+            // Safety: There are n values to move, and dst is
+            // borrowed separately. Its old values need no drop.
+            unsafe { self.take_front(dst, n) };
+            return n;
+        }
+        let (head, tail) = self.raw_slices(0..n);
+        self.start = to_index(self.slot(n));
+        self.len = to_index(self.len() - n);
+        let nhead = head.len();
+        // Safety: The slices hold values which are no longer
+        // part of the queue, and do not overlap dst, which is
+        // borrowed separately. Each value either is moved into
+        // dst or holds an old value of dst which is dropped.
+        unsafe {
+            ptr::swap_nonoverlapping(head as *mut T, dst, nhead);
+            ptr::swap_nonoverlapping(tail as *mut T, dst.add(nhead), n - nhead);
+            ptr::drop_in_place(head);
+            ptr::drop_in_place(tail);
+        }
+        n
+    }
+
+    /// Returns `Some` array of the first `N` values in the
+    /// queue, removing them, if the queue holds at least `N`
+    /// values, and `None` otherwise. In the latter case the
+    /// queue is not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, u8> = Queue::default();
+    /// q.copy_from_slice(b"abc").unwrap();
+    /// assert_eq!(Some(*b"ab"), q.extract_array::<2>());
+    /// assert_eq!(None, q.extract_array::<2>());
+    /// assert_eq!(1, q.len());
+    /// ```
+    pub fn extract_array<const N: usize>(&mut self) -> Option<[T; N]> {
+        if self.len() < N {
+            return None;
+        }
+        let mut array = MaybeUninit::<[T; N]>::uninit();
+        // Safety: There are at least N values, and the array
+        // has room for them.
+        unsafe { self.take_front(array.as_mut_ptr() as *mut T, N) };
+        // Safety: All N elements were initialized above.
+        Some(unsafe { array.assume_init() })
+    }
+
+    /// Insert clones of `value` into the back of the queue
+    /// until it is full. The existing values are unchanged.
+    /// The last slot filled receives `value` itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// q.insert(1).unwrap();
+    /// q.fill(0);
+    /// assert_eq!(vec![1, 0, 0], q.iter().copied().collect::<Vec<_>>());
+    /// ```
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        let n = self.remaining_capacity();
+        for _ in 1..n {
+            // Safety: The queue is not yet full.
+            unsafe { self.push_unchecked(value.clone()) };
+        }
+        if n > 0 {
+            // Safety: One slot remains.
+            unsafe { self.push_unchecked(value) };
+        }
+    }
+
+    /// Insert values returned by successive calls to `f` into
+    /// the back of the queue until it is full. The existing
+    /// values are unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// let mut next = 0;
+    /// q.fill_with(|| {
+    ///     next += 1;
+    ///     next
+    /// });
+    /// assert_eq!(vec![1, 2, 3], q.iter().copied().collect::<Vec<_>>());
+    /// ```
+    pub fn fill_with(&mut self, mut f: impl FnMut() -> T) {
+        while !self.is_full() {
+            let value = f();
+            // Safety: The queue is not full.
+            unsafe { self.push_unchecked(value) };
+        }
+    }
+
+    /// Change the length of the queue to `n`. If `n` is
+    /// greater than the current length, values returned by
+    /// successive calls to `f` are inserted at the back;
+    /// otherwise values are dropped from the back as with
+    /// [Queue::truncate].
+    ///
+    /// # Errors
+    ///
+    /// Returns [QueueError::Overflow] if `n` is greater than
+    /// the capacity. In this case the queue is not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// q.resize_with(2, Default::default).unwrap();
+    /// assert_eq!(vec![0, 0], q.iter().copied().collect::<Vec<_>>());
+    /// assert!(q.resize_with(4, Default::default).is_err());
+    /// q.resize_with(1, Default::default).unwrap();
+    /// assert_eq!(1, q.len());
+    /// ```
+    pub fn resize_with(&mut self, n: usize, mut f: impl FnMut() -> T) -> Result<(), QueueError> {
+        if n > self.capacity() {
+            return Err(QueueError::Overflow);
+        }
+        if n < self.len() {
+            self.truncate(n);
+        }
+        while self.len() < n {
+            let value = f();
+            // Safety: The queue is not full, since len is less
+            // than n.
+            unsafe { self.push_unchecked(value) };
+        }
+        Ok(())
+    }
+
+    /// Consume the queue, returning a queue of the same
+    /// capacity holding the results of applying `f` to each
+    /// value, in order.
+    ///
+    /// If `f` panics, all of the values, converted or not,
+    /// are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// q.insert(1).unwrap();
+    /// q.insert(2).unwrap();
+    /// let q = q.map(|v| v.to_string());
+    /// assert_eq!(vec!["1", "2"], q.iter().collect::<Vec<_>>());
+    /// ```
+    pub fn map<U>(mut self, mut f: impl FnMut(T) -> U) -> Queue<C, U, Ix> {
+        let mut result = Queue::default();
+        while let Some(value) = self.extract() {
+            let value = f(value);
+            // Safety: result has the same capacity as self.
+            unsafe { result.push_unchecked(value) };
+        }
+        result
+    }
+
+    /// Drop all values in the queue, leaving it empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, String> = Queue::default();
+    /// q.insert("a".to_string()).unwrap();
+    /// q.insert("b".to_string()).unwrap();
+    /// q.clear();
+    /// assert!(q.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        let (head, tail) = self.as_mut_slices();
+        let (head, tail) = (head as *mut [T], tail as *mut [T]);
+        self.start = to_index(0);
+        self.len = to_index(0);
+        // Safety: The slices hold the previously inserted
+        // values, which are no longer part of the queue.
+        unsafe {
+            ptr::drop_in_place(head);
+            ptr::drop_in_place(tail);
+        }
+    }
+
+    /// Drop values from the back of the queue until at most
+    /// `n` remain. Does nothing if the queue length is already
+    /// at most `n`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// for i in 0..3 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// q.truncate(1);
+    /// assert_eq!(1, q.len());
+    /// assert_eq!(Some(&0), q.back());
+    /// ```
+    pub fn truncate(&mut self, n: usize) {
+        if n >= self.len() {
+            return;
+        }
+        let (head, tail) = self.raw_slices(n..self.len());
+        self.len = to_index(n);
+        // Safety: The slices hold the previously inserted
+        // values, which are no longer part of the queue.
+        unsafe {
+            ptr::drop_in_place(head);
+            ptr::drop_in_place(tail);
+        }
+    }
+
+    /// Drop up to `n` values from the front of the queue.
+    /// Returns the number of values dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// for i in 0..3 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// assert_eq!(2, q.drop_front(2));
+    /// assert_eq!(Some(&2), q.front());
+    /// assert_eq!(1, q.drop_front(5));
+    /// assert!(q.is_empty());
+    /// ```
+    pub fn drop_front(&mut self, n: usize) -> usize {
+        let n = n.min(self.len());
+        if n == 0 {
+            return 0;
+        }
+        let (head, tail) = self.raw_slices(0..n);
+        self.start = to_index(self.slot(n));
+        self.len = to_index(self.len() - n);
+        // Safety: The slices hold the previously inserted
+        // values, which are no longer part of the queue.
+        unsafe {
+            ptr::drop_in_place(head);
+            ptr::drop_in_place(tail);
+        }
+        n
+    }
+
+    /// Retain only the values for which `f` returns `true`,
+    /// dropping the rest. The retained values keep their
+    /// order. Each value is visited exactly once, from front
+    /// to back.
+    ///
+    /// If `f` panics, the value being visited and all
+    /// unvisited values are retained.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, usize> = Queue::default();
+    /// for i in 0..4 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// q.retain(|&v| v % 2 == 1);
+    /// assert_eq!(Some(1), q.extract());
+    /// assert_eq!(Some(3), q.extract());
+    /// assert!(q.is_empty());
+    /// ```
+    pub fn retain(&mut self, mut f: impl FnMut(&T) -> bool) {
+        self.retain_mut(|v| f(v));
+    }
+
+    /// Retain only the values for which `f` returns `true`,
+    /// dropping the rest, as with [Queue::retain]. `f` may
+    /// modify the values it visits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, usize> = Queue::default();
+    /// for i in 1..=4 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// q.retain_mut(|ttl| {
+    ///     *ttl -= 1;
+    ///     *ttl > 1
+    /// });
+    /// assert_eq!(Some(2), q.extract());
+    /// assert_eq!(Some(3), q.extract());
+    /// assert!(q.is_empty());
+    /// ```
+    pub fn retain_mut(&mut self, mut f: impl FnMut(&mut T) -> bool) {
+        let mut c = Compactor::new(self);
+        while c.processed < c.len {
+            let value = c.value(c.processed);
+            // Safety: Unprocessed values have not been moved.
+            if f(unsafe { &mut *value }) {
+                c.keep();
+            } else {
+                c.discard();
+            }
+        }
+    }
+
+    /// Remove consecutive repeated values from the queue,
+    /// keeping the first of each run of equal values. The
+    /// remaining values keep their order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<5, usize> = Queue::default();
+    /// q.copy_from_slice(&[1, 1, 2, 2, 1]).unwrap();
+    /// q.dedup();
+    /// assert_eq!(vec![1, 2, 1], q.iter().copied().collect::<Vec<_>>());
+    /// ```
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        let mut c = Compactor::new(self);
+        if c.len == 0 {
+            return;
+        }
+        c.keep();
+        while c.processed < c.len {
+            let value = c.value(c.processed);
+            let last = c.value(c.processed - c.deleted - 1);
+            // Safety: Unprocessed values have not been moved,
+            // and the last kept value is in its final position.
+            if unsafe { *value == *last } {
+                c.discard();
+            } else {
+                c.keep();
+            }
+        }
+    }
+
+    /// Removes the values at the logical positions in
+    /// `range` from the queue, returning an iterator over
+    /// them from front to back. The values outside the range
+    /// keep their order.
+    ///
+    /// When the iterator is dropped, any values in the range
+    /// that it has not yet yielded are dropped. If the
+    /// iterator is leaked (for example with
+    /// [core::mem::forget]), the queue may lose values beyond
+    /// the range as well.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the
+    /// end, or the end is greater than the queue length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, usize> = Queue::default();
+    /// for i in 0..4 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// assert_eq!(3, q.drain(1..3).sum::<usize>());
+    /// assert_eq!(Some(0), q.extract());
+    /// assert_eq!(Some(3), q.extract());
+    /// ```
+    pub fn drain(&mut self, range: impl RangeBounds<usize>) -> Drain<'_, C, T, Ix> {
+        let range = self.logical_range(range);
+        let len = self.len();
+        // Until the Drain is dropped, the queue owns only the
+        // values before the range.
+        self.len = to_index(range.start);
+        Drain {
+            queue: self,
+            start: range.start,
+            end: range.end,
+            front: range.start,
+            back: range.end,
+            len,
+        }
+    }
+
+    /// Returns an iterator which visits the values of the
+    /// queue from front to back, removing and yielding those
+    /// for which `f` returns `true`. The remaining values keep
+    /// their order.
+    ///
+    /// If the iterator is dropped before it is exhausted, the
+    /// values it has not yet visited are kept in the queue.
+    /// If the iterator is leaked (for example with
+    /// [core::mem::forget]), the queue may lose all its
+    /// values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, usize> = Queue::default();
+    /// for i in 0..4 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// let mut odd: Queue<4, usize> = Queue::default();
+    /// for v in q.extract_if(|v| *v % 2 == 1) {
+    ///     odd.insert(v).unwrap();
+    /// }
+    /// assert_eq!(Some(1), odd.extract());
+    /// assert_eq!(Some(0), q.extract());
+    /// assert_eq!(Some(2), q.extract());
+    /// ```
+    pub fn extract_if<F>(&mut self, f: F) -> ExtractIf<'_, C, T, F, Ix>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let len = self.len();
+        // Until the ExtractIf is dropped, the queue is in an
+        // inconsistent state: make it own nothing.
+        self.len = to_index(0);
+        ExtractIf {
+            queue: self,
+            len,
+            processed: 0,
+            deleted: 0,
+            f,
+        }
+    }
+
+    /// Returns an iterator over references to the values in
+    /// the queue, from front to back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, u32> = Queue::default();
+    /// for i in 1..=4 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// assert_eq!(10, q.iter().sum::<u32>());
+    /// ```
+    pub fn iter(&self) -> Iter<'_, C, T, Ix> {
+        Iter {
+            queue: self,
+            front: 0,
+            back: self.len(),
+        }
+    }
+
+    /// Returns an iterator over mutable references to the
+    /// values in the queue, from front to back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, u32> = Queue::default();
+    /// for i in 1..=4 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// for v in q.iter_mut() {
+    ///     *v *= 2;
+    /// }
+    /// assert_eq!(Some(2), q.extract());
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, C, T, Ix> {
+        IterMut {
+            values: self.values.as_mut_ptr(),
+            start: self.start,
+            front: 0,
+            back: self.len(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over references to the values at
+    /// the logical positions in `range`, from front to back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the
+    /// end, or the end is greater than the queue length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, u32> = Queue::default();
+    /// for i in 1..=4 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// assert_eq!(7, q.range(2..).sum::<u32>());
+    /// ```
+    pub fn range(&self, range: impl RangeBounds<usize>) -> Iter<'_, C, T, Ix> {
+        let range = self.logical_range(range);
+        Iter {
+            queue: self,
+            front: range.start,
+            back: range.end,
+        }
+    }
+
+    /// Returns an iterator over mutable references to the
+    /// values at the logical positions in `range`, from front
+    /// to back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the
+    /// end, or the end is greater than the queue length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, u32> = Queue::default();
+    /// for i in 1..=4 {
+    ///     q.insert(i).unwrap();
+    /// }
+    /// for v in q.range_mut(..2) {
+    ///     *v = 0;
+    /// }
+    /// assert_eq!(7, q.iter().sum::<u32>());
+    /// ```
+    pub fn range_mut(&mut self, range: impl RangeBounds<usize>) -> IterMut<'_, C, T, Ix> {
+        let range = self.logical_range(range);
+        IterMut {
+            values: self.values.as_mut_ptr(),
+            start: self.start,
+            front: range.start,
+            back: range.end,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Sort the values of the queue into ascending order.
+    /// The sort is stable: equal values keep their relative
+    /// order.
+    ///
+    /// The values are first made contiguous, then sorted with
+    /// an insertion sort, which needs no heap storage but
+    /// takes time quadratic in the queue length. For long
+    /// queues, consider
+    /// `q.make_contiguous().sort_unstable()` instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// q.copy_from_slice(&[3, 1, 2]).unwrap();
+    /// q.sort();
+    /// assert_eq!(vec![1, 2, 3], q.iter().copied().collect::<Vec<_>>());
+    /// ```
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    /// Sort the values of the queue with the comparison
+    /// function `compare`, as with [Queue::sort].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// q.copy_from_slice(&[3, 1, 2]).unwrap();
+    /// q.sort_by(|a, b| b.cmp(a));
+    /// assert_eq!(vec![3, 2, 1], q.iter().copied().collect::<Vec<_>>());
+    /// ```
+    pub fn sort_by(&mut self, mut compare: impl FnMut(&T, &T) -> Ordering) {
+        let values = self.make_contiguous();
+        for i in 1..values.len() {
+            let mut j = i;
+            while j > 0 && compare(&values[j - 1], &values[j]) == Ordering::Greater {
+                values.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+    }
+
+    /// Sort the values of the queue by the keys extracted
+    /// by `f`, as with [Queue::sort].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, i32> = Queue::default();
+    /// q.copy_from_slice(&[-3, 1, -2]).unwrap();
+    /// q.sort_by_key(|v| v.abs());
+    /// assert_eq!(vec![1, -2, -3], q.iter().copied().collect::<Vec<_>>());
+    /// ```
+    pub fn sort_by_key<K: Ord>(&mut self, mut f: impl FnMut(&T) -> K) {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+
+    /// Binary search the queue, which must be sorted in
+    /// ascending order, for `value`. Returns `Ok` with the
+    /// index of a matching value, or `Err` with the index at
+    /// which `value` could be inserted to keep the queue
+    /// sorted. If several values match, any one of their
+    /// indices may be returned.
+    ///
+    /// The search works on logical indices, so the queue
+    /// need not be contiguous.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, usize> = Queue::default();
+    /// q.copy_from_slice(&[1, 3, 5]).unwrap();
+    /// assert_eq!(Ok(1), q.binary_search(&3));
+    /// assert_eq!(Err(2), q.binary_search(&4));
+    /// ```
+    pub fn binary_search(&self, value: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|v| v.cmp(value))
+    }
+
+    /// Binary search the queue with the comparison function
+    /// `f`, which should return the ordering of its argument
+    /// relative to the target, as with [Queue::binary_search].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, usize> = Queue::default();
+    /// q.copy_from_slice(&[1, 3, 5]).unwrap();
+    /// assert_eq!(Ok(2), q.binary_search_by(|v| v.cmp(&5)));
+    /// assert_eq!(Err(0), q.binary_search_by(|v| v.cmp(&0)));
+    /// ```
+    pub fn binary_search_by(&self, mut f: impl FnMut(&T) -> Ordering) -> Result<usize, usize> {
+        let mut low = 0;
+        let mut high = self.len();
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match f(&self[mid]) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Ok(mid),
+            }
+        }
+        Err(low)
+    }
+
+    /// Binary search the queue, which must be sorted by the
+    /// keys extracted by `f`, for `key`, as with
+    /// [Queue::binary_search].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, (usize, char)> = Queue::default();
+    /// q.copy_from_slice(&[(1, 'a'), (3, 'b'), (5, 'c')]).unwrap();
+    /// assert_eq!(Ok(1), q.binary_search_by_key(&3, |&(k, _)| k));
+    /// ```
+    pub fn binary_search_by_key<K: Ord>(
+        &self,
+        key: &K,
+        mut f: impl FnMut(&T) -> K,
+    ) -> Result<usize, usize> {
+        self.binary_search_by(|v| f(v).cmp(key))
+    }
+
+    /// Returns the index of the first value for which `pred`
+    /// is false, assuming that the queue is partitioned so
+    /// that all values satisfying `pred` come first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, usize> = Queue::default();
+    /// q.copy_from_slice(&[1, 2, 3, 4]).unwrap();
+    /// assert_eq!(2, q.partition_point(|&v| v < 3));
+    /// ```
+    pub fn partition_point(&self, mut pred: impl FnMut(&T) -> bool) -> usize {
+        self.binary_search_by(|v| {
+            if pred(v) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        })
+        .unwrap_or_else(|i| i)
+    }
+
+    /// Returns the capacity of this queue (maximum number
+    /// of values that may be stored) as defined at
+    /// compile-time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// assert_eq!(3, q.capacity());
+    /// assert_eq!(Queue::<3, usize>::CAPACITY, q.capacity());
+    /// ```
+    pub const fn capacity(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns the number of values currently stored in the
+    /// queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// q.insert(17).unwrap();
+    /// assert_eq!(1, q.len());
+    /// ```
+    pub const fn len(&self) -> usize {
+        index_to_usize(&self.len)
+    }
+
+    /// Returns the number of additional values that may be
+    /// inserted into the queue before it is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<4, u8> = Queue::default();
+    /// q.insert(0).unwrap();
+    /// let data = [1, 2, 3, 4, 5];
+    /// let n = q.remaining_capacity().min(data.len());
+    /// for &b in &data[..n] {
+    ///     q.insert(b).unwrap();
+    /// }
+    /// assert_eq!(3, n);
+    /// assert!(q.is_full());
+    /// ```
+    pub const fn remaining_capacity(&self) -> usize {
+        self.values.len() - self.len()
+    }
+
+    /// Returns `false` if the queue contains values, but
+    /// `true` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<3, usize> = Queue::default();
+    /// assert!(q.is_empty());
+    /// q.insert(17).unwrap();
+    /// assert!(!q.is_empty());
+    /// ```
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the queue is filled to capacity,
+    /// but `false` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smallqueue::Queue;
+    /// let mut q: Queue<2, usize> = Queue::default();
+    /// assert!(!q.is_full());
+    /// q.insert(17).unwrap();
+    /// assert!(!q.is_full());
+    /// q.insert(18).unwrap();
+    /// assert!(q.is_full());
+    /// ```
+    pub const fn is_full(&self) -> bool {
+        self.len() == self.values.len()
+    }
+
+    /// Logical positions described by `range`, checked
+    /// against the queue length.
+    fn logical_range(&self, range: impl RangeBounds<usize>) -> Range<usize> {
+        let start = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i.checked_add(1).expect("range start overflow"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&i) => i.checked_add(1).expect("range end overflow"),
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => self.len(),
+        };
+        if start > end {
+            panic!("range starts at {start} but ends at {end}");
+        }
+        if end > self.len() {
+            panic!(
+                "range end {end} out of bounds for queue of length {}",
+                self.len()
+            );
+        }
+        start..end
+    }
+
+    /// Insert `value` into the slot after the last value.
+    ///
+    /// # Safety
+    ///
+    /// The queue must not be full.
+    #[inline]
+    unsafe fn push_unchecked(&mut self, value: T) {
+        debug_assert!(!self.is_full());
+        let slot = self.slot(self.len());
+        // Safety: start is always less than the capacity, and
+        // len is less than the capacity since the queue is not
+        // full. Their sum is thus less than twice the capacity,
+        // which wrap reduces to an index in bounds.
+        unsafe { self.values.get_unchecked_mut(slot).write(value) };
+        self.len = to_index(self.len() + 1);
+    }
+
+    /// Copy `n` values from `src` into the slots after the
+    /// last value, in at most two copies, and make them part
+    /// of the queue.
+    ///
+    /// # Safety
+    ///
+    /// There must be room for `n` more values, and `src` must
+    /// be valid for reading `n` values that do not overlap the
+    /// queue. The queue takes ownership of the copies.
+    unsafe fn put_back(&mut self, src: *const T, n: usize) {
+        let (head, tail) = self.spare_capacity_mut();
+        debug_assert!(n <= head.len() + tail.len());
+        let nhead = n.min(head.len());
+        // Safety: The spare capacity slices have room for all
+        // of src, split as computed.
+        unsafe {
+            ptr::copy_nonoverlapping(src, head.as_mut_ptr() as *mut T, nhead);
+            ptr::copy_nonoverlapping(src.add(nhead), tail.as_mut_ptr() as *mut T, n - nhead);
+        }
+        self.len = to_index(self.len() + n);
+    }
+
+    /// Move the first `n` values of the queue into `dst`, in
+    /// order and in at most two copies, and remove them from
+    /// the queue.
+    ///
+    /// # Safety
+    ///
+    /// The queue must hold at least `n` values, and `dst` must
+    /// be valid for writing `n` values that do not overlap the
+    /// queue. Any values previously at `dst` are overwritten
+    /// without being dropped.
+    unsafe fn take_front(&mut self, dst: *mut T, n: usize) {
+        debug_assert!(n <= self.len());
+        if n == 0 {
+            return;
+        }
+        let (head, tail) = self.raw_slices(0..n);
+        // Safety: The slices hold exactly n values, which are
+        // removed from the queue below.
+        unsafe {
+            ptr::copy_nonoverlapping(head as *const T, dst, head.len());
+            ptr::copy_nonoverlapping(tail as *const T, dst.add(head.len()), tail.len());
+        }
+        self.start = to_index(self.slot(n));
+        self.len = to_index(self.len() - n);
+    }
+
+    /// Move the values of this queue, in order, into a new
+    /// queue of capacity `D`.
+    ///
+    /// # Safety
+    ///
+    /// The queue must hold at most `D` values.
+    unsafe fn move_into<const D: usize>(mut self) -> Queue<D, T, Ix> {
+        debug_assert!(self.len() <= D);
+        let mut q = Queue::new();
+        let n = self.len();
+        // Safety: The new queue is empty, so its first n slots
+        // are free by the caller's guarantee.
+        unsafe { self.take_front(q.values.as_mut_ptr() as *mut T, n) };
+        q.len = to_index(n);
+        q
+    }
+
+    /// Move the first `n` values of a non-full queue to its
+    /// back, one at a time.
+    fn rotate_forward(&mut self, n: usize) {
+        for _ in 0..n {
+            self.shift(0, self.len());
+            self.start = to_index(self.slot(1));
+        }
+    }
+
+    /// Move the last `n` values of a non-full queue to its
+    /// front, one at a time.
+    fn rotate_backward(&mut self, n: usize) {
+        for _ in 0..n {
+            self.start = to_index(Self::wrap(self.start() + C - 1));
+            self.shift(self.len(), 0);
+        }
+    }
+
+    /// Move the value at logical position `src` to logical
+    /// position `dst`, leaving `src` logically uninitialized.
+    /// Any value at `dst` is overwritten without being
+    /// dropped.
+    fn shift(&mut self, src: usize, dst: usize) {
+        let (src, dst) = (self.slot(src), self.slot(dst));
+        let values = self.values.as_mut_ptr();
+        // Safety: Both slots are in bounds; the caller is
+        // responsible for the initialization state.
+        unsafe { ptr::copy(values.add(src), values.add(dst), 1) };
+    }
+
+    /// Raw slices over the values at the logical positions in
+    /// `range`, in order, which must be within the queue
+    /// length. The second slice is empty unless the range
+    /// wraps around the end of the backing array.
+    fn raw_slices(&mut self, range: Range<usize>) -> (*mut [T], *mut [T]) {
+        let values = self.values.as_mut_ptr() as *mut T;
+        if range.is_empty() {
+            return (
+                ptr::slice_from_raw_parts_mut(values, 0),
+                ptr::slice_from_raw_parts_mut(values, 0),
+            );
+        }
+        let start = self.slot(range.start);
+        let head_len = range.len().min(self.values.len() - start);
+        (
+            // Safety: start is in bounds of the values array.
+            ptr::slice_from_raw_parts_mut(unsafe { values.add(start) }, head_len),
+            ptr::slice_from_raw_parts_mut(values, range.len() - head_len),
+        )
+    }
+
+    /// Physical index ranges in `values` of the front and
+    /// wrapped parts of the queue contents.
+    fn occupied(&self) -> (Range<usize>, Range<usize>) {
+        let head_len = self.len().min(self.values.len() - self.start());
+        (
+            self.start()..self.start() + head_len,
+            0..self.len() - head_len,
+        )
+    }
+
+    /// Whether the capacity is a power of two, so that
+    /// [Queue::wrap] can mask.
+    const IS_POW2: bool = C.is_power_of_two();
+
+    /// Physical index in `values` of the first value.
+    #[inline]
+    const fn start(&self) -> usize {
+        index_to_usize(&self.start)
+    }
+
+    /// Physical index in `values` of logical position
+    /// `index` from the front of the queue. Since `start` is
+    /// less than the capacity, `index` must be at most the
+    /// capacity.
+    #[inline]
+    fn slot(&self, index: usize) -> usize {
+        Self::wrap(self.start() + index)
+    }
+
+    /// Physical index in `values` corresponding to the
+    /// possibly out-of-range physical index `index`, which
+    /// must be less than twice the capacity. For capacities
+    /// that are powers of two this is a mask; otherwise it is
+    /// a conditional subtraction, which suffices because of
+    /// the bound on `index`. Neither needs a division.
+    #[inline]
+    fn wrap(index: usize) -> usize {
+        debug_assert!(index < 2 * C);
+        if Self::IS_POW2 {
+            index & (C - 1)
+        } else if index >= C {
+            index - C
+        } else {
+            index
+        }
+    }
+}
+
+impl<const C: usize, T, Ix: QueueIndex> Default for Queue<C, T, Ix> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Clone the values of the queue, in order, into a new
+/// queue whose values start at the front of its storage.
+/// If cloning a value panics, the values already cloned are
+/// dropped.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// let mut q: Queue<3, usize> = Queue::default();
+/// q.copy_from_slice(&[1, 2]).unwrap();
+/// let mut r = q.clone();
+/// r.insert(3).unwrap();
+/// assert_eq!(2, q.len());
+/// assert_eq!(3, r.len());
+/// ```
+impl<const C: usize, T: Clone, Ix: QueueIndex> Clone for Queue<C, T, Ix> {
+    fn clone(&self) -> Self {
+        let mut q = Self::default();
+        q.clone_from(self);
+        q
+    }
+
+    /// Reuses the values already in `self` where possible,
+    /// via [Clone::clone_from], dropping or cloning only the
+    /// difference in length.
+    fn clone_from(&mut self, source: &Self) {
+        let n = self.len().min(source.len());
+        self.truncate(n);
+        for (v, s) in self.iter_mut().zip(source.iter()) {
+            v.clone_from(s);
+        }
+        for v in source.iter().skip(n) {
+            // Safety: The queue holds fewer values than source,
+            // which has the same capacity.
+            unsafe { self.push_unchecked(v.clone()) };
+        }
+    }
+}
+
+/// Format the values of the queue as a list, from front
+/// to back.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// let mut q: Queue<3, usize> = Queue::default();
+/// q.copy_from_slice(&[1, 2]).unwrap();
+/// assert_eq!("[1, 2]", format!("{q:?}"));
+/// ```
+impl<const C: usize, T: fmt::Debug, Ix: QueueIndex> fmt::Debug for Queue<C, T, Ix> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Queues are equal when they hold equal values in the same
+/// order, regardless of capacity or storage layout.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// let mut q: Queue<3, usize> = Queue::default();
+/// let mut r: Queue<5, usize> = Queue::default();
+/// q.copy_from_slice(&[1, 2]).unwrap();
+/// r.copy_from_slice(&[1, 2]).unwrap();
+/// assert_eq!(q, r);
+/// ```
+impl<const C: usize, const D: usize, T: PartialEq, Ix: QueueIndex, Jx: QueueIndex>
+    PartialEq<Queue<D, T, Jx>> for Queue<C, T, Ix>
+{
+    fn eq(&self, other: &Queue<D, T, Jx>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<const C: usize, T: Eq, Ix: QueueIndex> Eq for Queue<C, T, Ix> {}
+
+/// Queues are ordered lexicographically by their values
+/// from front to back, as with slices.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// let mut q: Queue<3, usize> = Queue::default();
+/// let mut r: Queue<3, usize> = Queue::default();
+/// q.copy_from_slice(&[1, 2]).unwrap();
+/// r.copy_from_slice(&[1, 2, 3]).unwrap();
+/// assert!(q < r);
+/// ```
+impl<const C: usize, T: PartialOrd, Ix: QueueIndex> PartialOrd for Queue<C, T, Ix> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<const C: usize, T: Ord, Ix: QueueIndex> Ord for Queue<C, T, Ix> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+/// Hash the queue length followed by its values from front
+/// to back, so that equal queues hash equally whatever their
+/// storage layout. The length prefix keeps the encoding
+/// prefix-free, so that for example a pair of queues holding
+/// `[1]` and `[]` hashes differently from one holding `[]`
+/// and `[1]`. The hash is not guaranteed to match that of the
+/// equivalent slice.
+impl<const C: usize, T: Hash, Ix: QueueIndex> Hash for Queue<C, T, Ix> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for v in self.iter() {
+            v.hash(state);
+        }
+    }
+}
+
+/// A queue is equal to a slice or array holding equal
+/// values in the same order.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// let mut q: Queue<3, usize> = Queue::default();
+/// q.copy_from_slice(&[1, 2]).unwrap();
+/// assert_eq!(q, [1, 2]);
+/// assert_eq!(q, &[1, 2][..]);
+/// assert_eq!([1, 2], q);
+/// ```
+impl<const C: usize, T: PartialEq<U>, U, Ix: QueueIndex> PartialEq<[U]> for Queue<C, T, Ix> {
+    fn eq(&self, other: &[U]) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+        let (head, tail) = self.as_slices();
+        let (other_head, other_tail) = other.split_at(head.len());
+        head == other_head && tail == other_tail
+    }
+}
+
+impl<const C: usize, T: PartialEq<U>, U, Ix: QueueIndex> PartialEq<&[U]> for Queue<C, T, Ix> {
+    fn eq(&self, other: &&[U]) -> bool {
+        *self == **other
+    }
+}
+
+impl<const C: usize, T: PartialEq<U>, U, const N: usize, Ix: QueueIndex> PartialEq<[U; N]>
+    for Queue<C, T, Ix>
+{
+    fn eq(&self, other: &[U; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<const C: usize, T: PartialEq<U>, U, Ix: QueueIndex> PartialEq<Queue<C, U, Ix>> for [T] {
+    fn eq(&self, other: &Queue<C, U, Ix>) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+        let (other_head, other_tail) = other.as_slices();
+        let (head, tail) = self.split_at(other_head.len());
+        head == other_head && tail == other_tail
+    }
+}
+
+impl<const C: usize, T: PartialEq<U>, U, Ix: QueueIndex> PartialEq<Queue<C, U, Ix>> for &[T] {
+    fn eq(&self, other: &Queue<C, U, Ix>) -> bool {
+        **self == *other
+    }
+}
+
+impl<const C: usize, T: PartialEq<U>, U, const N: usize, Ix: QueueIndex> PartialEq<Queue<C, U, Ix>>
+    for [T; N]
+{
+    fn eq(&self, other: &Queue<C, U, Ix>) -> bool {
+        self[..] == *other
+    }
+}
+
+/// Make a full queue holding the values of an array, in
+/// order.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// let q = Queue::<3, _>::from([1, 2, 3]);
+/// assert!(q.is_full());
+/// assert_eq!(q, [1, 2, 3]);
+///
+/// let q: Queue<3, usize, u8> = Queue::from([1, 2, 3]);
+/// assert_eq!(q, [1, 2, 3]);
+/// ```
+impl<const C: usize, T, Ix: QueueIndex> From<[T; C]> for Queue<C, T, Ix> {
+    fn from(values: [T; C]) -> Self {
+        let mut q = Self::new();
+        q.values = values.map(MaybeUninit::new);
+        q.len = to_index(C);
+        q
+    }
+}
+
+/// Make a queue holding clones of the values of a slice, in
+/// order.
+///
+/// # Errors
+///
+/// Returns [QueueError::Overflow], without cloning anything,
+/// if the slice is longer than the queue capacity.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::{Queue, QueueError};
+/// let q = Queue::<3, usize>::try_from(&[1, 2][..]).unwrap();
+/// assert_eq!(q, [1, 2]);
+/// let r = Queue::<3, usize>::try_from(&[1, 2, 3, 4][..]);
+/// assert!(matches!(r, Err(QueueError::Overflow)));
+/// ```
+impl<const C: usize, T: Clone, Ix: QueueIndex> TryFrom<&[T]> for Queue<C, T, Ix> {
+    type Error = QueueError;
+
+    fn try_from(values: &[T]) -> Result<Self, QueueError> {
+        let mut q = Self::default();
+        q.extend_from_slice(values)?;
+        Ok(q)
+    }
+}
+
+/// Collect values into a queue, in order.
+///
+/// # Panics
+///
+/// Panics if the iterator yields more values than the queue
+/// capacity. See [Queue::try_from_iter] and
+/// [Queue::from_iter_truncated] for alternatives.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// let q: Queue<8, usize> = (0..5).collect();
+/// assert_eq!(5, q.len());
+/// assert_eq!(Some(&0), q.front());
+/// ```
+impl<const C: usize, T, Ix: QueueIndex> FromIterator<T> for Queue<C, T, Ix> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut q = Self::default();
+        q.extend(iter);
+        q
+    }
+}
+
+/// Insert values into the back of the queue, in order.
+///
+/// # Panics
+///
+/// Panics if the iterator yields more values than there is
+/// room for. The values inserted before the panic remain in
+/// the queue. See [Queue::insert_many] for an alternative.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// let mut q: Queue<4, usize> = Queue::default();
+/// q.extend([1, 2]);
+/// q.extend(&[3, 4]);
+/// assert_eq!(q, [1, 2, 3, 4]);
+/// ```
+impl<const C: usize, T, Ix: QueueIndex> Extend<T> for Queue<C, T, Ix> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for v in iter {
+            if self.is_full() {
+                panic!("queue capacity {C} exceeded");
+            }
+            // Safety: The queue is not full.
+            unsafe { self.push_unchecked(v) };
+        }
+    }
+}
+
+impl<'a, const C: usize, T: Copy + 'a, Ix: QueueIndex> Extend<&'a T> for Queue<C, T, Ix> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+/// Write UTF-8 bytes into the back of a byte queue.
+///
+/// Each string is written all-or-nothing: if its bytes do not
+/// all fit, none are written and [fmt::Error] is returned.
+/// Note that `write!` may make several writes for a single
+/// format string, so a failing `write!` may still leave part
+/// of its output in the queue.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// use core::fmt::Write;
+///
+/// let mut q: Queue<8, u8> = Queue::default();
+/// write!(q, "t={}", 42).unwrap();
+/// assert_eq!(q, *b"t=42");
+/// assert!(q.write_str("12345").is_err());
+/// assert_eq!(4, q.len());
+/// ```
+impl<const C: usize, Ix: QueueIndex> fmt::Write for Queue<C, u8, Ix> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.copy_from_slice(s.as_bytes()).map_err(|_| fmt::Error)
+    }
+}
+
+/// Write characters into the back of a character queue.
+/// Each string is written all-or-nothing, as for byte
+/// queues.
+impl<const C: usize, Ix: QueueIndex> fmt::Write for Queue<C, char, Ix> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.chars().count() > self.remaining_capacity() {
+            return Err(fmt::Error);
+        }
+        for c in s.chars() {
+            // Safety: There is room for all of the characters.
+            unsafe { self.push_unchecked(c) };
+        }
+        Ok(())
+    }
+}
+
+/// Index the queue by logical position from the front, as
+/// with [Queue::get].
+///
+/// # Panics
+///
+/// Panics if `index` is not less than the queue length.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// let mut q: Queue<3, usize> = Queue::default();
+/// q.insert(17).unwrap();
+/// q.insert(18).unwrap();
+/// assert_eq!(18, q[1]);
+/// ```
+impl<const C: usize, T, Ix: QueueIndex> Index<usize> for Queue<C, T, Ix> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        let len = self.len();
+        self.get(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {len} but the index is {index}")
+        })
+    }
+}
+
+/// Mutably index the queue by logical position from the
+/// front, as with [Queue::get_mut].
+///
+/// # Panics
+///
+/// Panics if `index` is not less than the queue length.
+impl<const C: usize, T, Ix: QueueIndex> IndexMut<usize> for Queue<C, T, Ix> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len();
+        self.get_mut(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {len} but the index is {index}")
+        })
+    }
+}
+
+impl<const C: usize, T, Ix: QueueIndex> Drop for Queue<C, T, Ix> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+/// Iterator over references to the values of a [Queue],
+/// from front to back. Created by [Queue::iter].
+pub struct Iter<'a, const C: usize, T, Ix: QueueIndex = usize> {
+    queue: &'a Queue<C, T, Ix>,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, const C: usize, T, Ix: QueueIndex> Iterator for Iter<'a, C, T, Ix> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.front == self.back {
+            return None;
+        }
+        let value = self.queue.get(self.front);
+        self.front += 1;
+        value
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+}
+
+impl<const C: usize, T, Ix: QueueIndex> Clone for Iter<'_, C, T, Ix> {
+    fn clone(&self) -> Self {
+        Iter {
+            queue: self.queue,
+            front: self.front,
+            back: self.back,
+        }
+    }
+}
+
+impl<const C: usize, T, Ix: QueueIndex> DoubleEndedIterator for Iter<'_, C, T, Ix> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.queue.get(self.back)
+    }
+}
+
+impl<const C: usize, T, Ix: QueueIndex> ExactSizeIterator for Iter<'_, C, T, Ix> {}
+
+/// Iterator over mutable references to the values of a
+/// [Queue], from front to back. Created by
+/// [Queue::iter_mut].
+pub struct IterMut<'a, const C: usize, T, Ix: QueueIndex = usize> {
+    // A raw pointer is used so that the references handed
+    // out do not alias a borrow of the whole array.
+    values: *mut MaybeUninit<T>,
+    start: Ix,
+    front: usize,
+    back: usize,
+    phantom: PhantomData<&'a mut T>,
+}
+
+impl<const C: usize, T, Ix: QueueIndex> IterMut<'_, C, T, Ix> {
+    /// Pointer to the value at logical position `index`.
+    fn value(&self, index: usize) -> *mut T {
+        let slot = Queue::<C, T, Ix>::wrap(index_to_usize(&self.start) + index);
+        // Safety: The slot is in bounds of the values array.
+        unsafe { self.values.add(slot) as *mut T }
+    }
+}
+
+impl<'a, const C: usize, T, Ix: QueueIndex> Iterator for IterMut<'a, C, T, Ix> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.front == self.back {
+            return None;
+        }
+        let value = self.value(self.front);
+        self.front += 1;
+        // Safety: Logical positions between front and back
+        // hold initialized values, and each is handed out at
+        // most once, so the references do not overlap.
+        Some(unsafe { &mut *value })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+}
+
+impl<const C: usize, T, Ix: QueueIndex> DoubleEndedIterator for IterMut<'_, C, T, Ix> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        // Safety: As for next().
+        Some(unsafe { &mut *self.value(self.back) })
+    }
+}
+
+impl<const C: usize, T, Ix: QueueIndex> ExactSizeIterator for IterMut<'_, C, T, Ix> {}
+
+// Safety: IterMut behaves like a `&mut [T]`.
+unsafe impl<const C: usize, T: Send, Ix: QueueIndex> Send for IterMut<'_, C, T, Ix> {}
+unsafe impl<const C: usize, T: Sync, Ix: QueueIndex> Sync for IterMut<'_, C, T, Ix> {}
+
+/// Iterator moving the values out of a [Queue], from front
+/// to back. Values not yet yielded are dropped along with
+/// the iterator. Created by [Queue::into_iter].
+pub struct IntoIter<const C: usize, T, Ix: QueueIndex = usize> {
+    queue: Queue<C, T, Ix>,
+}
+
+impl<const C: usize, T, Ix: QueueIndex> Iterator for IntoIter<C, T, Ix> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.queue.extract()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.queue.len(), Some(self.queue.len()))
+    }
+}
+
+impl<const C: usize, T, Ix: QueueIndex> DoubleEndedIterator for IntoIter<C, T, Ix> {
+    fn next_back(&mut self) -> Option<T> {
+        self.queue.pop_back()
+    }
+}
+
+impl<const C: usize, T, Ix: QueueIndex> ExactSizeIterator for IntoIter<C, T, Ix> {}
+
+/// Iterate over references to the values of the queue, as
+/// with [Queue::iter].
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// let mut q: Queue<3, usize> = Queue::default();
+/// q.copy_from_slice(&[0, 1, 2]).unwrap();
+/// q.extract().unwrap();
+/// q.insert(3).unwrap();
+/// let mut total = 0;
+/// for v in &q {
+///     total += v;
+/// }
+/// assert_eq!(6, total);
+/// ```
+impl<'a, const C: usize, T, Ix: QueueIndex> IntoIterator for &'a Queue<C, T, Ix> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, C, T, Ix>;
+
+    fn into_iter(self) -> Iter<'a, C, T, Ix> {
+        self.iter()
+    }
+}
+
+/// Iterate over mutable references to the values of the
+/// queue, as with [Queue::iter_mut].
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// let mut q: Queue<3, usize> = Queue::default();
+/// q.copy_from_slice(&[0, 1]).unwrap();
+/// for v in &mut q {
+///     *v += 10;
+/// }
+/// assert_eq!(q, [10, 11]);
+/// ```
+impl<'a, const C: usize, T, Ix: QueueIndex> IntoIterator for &'a mut Queue<C, T, Ix> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, C, T, Ix>;
+
+    fn into_iter(self) -> IterMut<'a, C, T, Ix> {
+        self.iter_mut()
+    }
+}
+
+/// Move the values out of the queue, from front to back.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// let mut q: Queue<3, String> = Queue::default();
+/// q.insert("a".to_string()).unwrap();
+/// q.insert("b".to_string()).unwrap();
+/// let mut s = String::new();
+/// for v in q {
+///     s += &v;
+/// }
+/// assert_eq!("ab", s);
+/// ```
+impl<const C: usize, T, Ix: QueueIndex> IntoIterator for Queue<C, T, Ix> {
+    type Item = T;
+    type IntoIter = IntoIter<C, T, Ix>;
+
+    fn into_iter(self) -> IntoIter<C, T, Ix> {
+        IntoIter { queue: self }
+    }
+}
+
+/// Removes values from a [Queue] in a single front-to-back
+/// pass, moving each kept value into place. Closes the gap
+/// left by removed values and fixes the queue length when
+/// dropped, even on panic.
+struct Compactor<'a, const C: usize, T, Ix: QueueIndex> {
+    queue: &'a mut Queue<C, T, Ix>,
+    // Original queue length.
+    len: usize,
+    // Number of values visited and removed.
+    processed: usize,
+    deleted: usize,
+}
+
+impl<'a, const C: usize, T, Ix: QueueIndex> Compactor<'a, C, T, Ix> {
+    fn new(queue: &'a mut Queue<C, T, Ix>) -> Self {
+        let len = queue.len();
+        Compactor {
+            queue,
+            len,
+            processed: 0,
+            deleted: 0,
+        }
+    }
+
+    /// Pointer to the slot at logical position `index`.
+    fn value(&mut self, index: usize) -> *mut T {
+        let slot = self.queue.slot(index);
+        self.queue.values[slot].as_mut_ptr()
+    }
+
+    /// Keep the next unprocessed value.
+    fn keep(&mut self) {
+        if self.deleted > 0 {
+            self.queue
+                .shift(self.processed, self.processed - self.deleted);
+        }
+        self.processed += 1;
+    }
+
+    /// Drop the next unprocessed value.
+    fn discard(&mut self) {
+        let value = self.value(self.processed);
+        self.processed += 1;
+        self.deleted += 1;
+        // Safety: Unprocessed values have not been moved, and
+        // this one is no longer part of the queue.
+        unsafe { ptr::drop_in_place(value) };
+    }
+}
+
+impl<const C: usize, T, Ix: QueueIndex> Drop for Compactor<'_, C, T, Ix> {
+    fn drop(&mut self) {
+        if self.deleted > 0 {
+            for i in self.processed..self.len {
+                self.queue.shift(i, i - self.deleted);
+            }
+        }
+        self.queue.len = to_index(self.len - self.deleted);
+    }
+}
+
+/// Iterator removing values from a [Queue], from front to
+/// back. Created by [Queue::drain].
+pub struct Drain<'a, const C: usize, T, Ix: QueueIndex = usize> {
+    queue: &'a mut Queue<C, T, Ix>,
+    // Logical range being drained.
+    start: usize,
+    end: usize,
+    // Logical range not yet yielded.
+    front: usize,
+    back: usize,
+    // Original queue length.
+    len: usize,
+}
+
+impl<const C: usize, T, Ix: QueueIndex> Drain<'_, C, T, Ix> {
+    /// Close the gap left by the drained range, moving
+    /// whichever of the values before or after it is fewer.
+    fn close_gap(&mut self) {
+        let n = self.end - self.start;
+        let queue = &mut *self.queue;
+        if self.start < self.len - self.end {
+            for i in (0..self.start).rev() {
+                queue.shift(i, i + n);
+            }
+            if n > 0 {
+                queue.start = to_index(queue.slot(n));
+            }
+        } else {
+            for i in self.end..self.len {
+                queue.shift(i, i - n);
+            }
+        }
+        queue.len = to_index(self.len - n);
+    }
+}
+
+impl<const C: usize, T, Ix: QueueIndex> Iterator for Drain<'_, C, T, Ix> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        let slot = self.queue.slot(self.front);
+        self.front += 1;
+        // Safety: Logical positions between front and back
+        // hold values that have not been yielded.
+        Some(unsafe { ptr::read(self.queue.values[slot].as_ptr()) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+}
+
+impl<const C: usize, T, Ix: QueueIndex> DoubleEndedIterator for Drain<'_, C, T, Ix> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        let slot = self.queue.slot(self.back);
+        // Safety: As for next().
+        Some(unsafe { ptr::read(self.queue.values[slot].as_ptr()) })
+    }
+}
+
+impl<const C: usize, T, Ix: QueueIndex> ExactSizeIterator for Drain<'_, C, T, Ix> {}
+
+impl<const C: usize, T, Ix: QueueIndex> Drop for Drain<'_, C, T, Ix> {
+    fn drop(&mut self) {
+        // Closes the gap even if dropping a value panics.
+        struct Guard<'r, 'a, const C: usize, T, Ix: QueueIndex>(&'r mut Drain<'a, C, T, Ix>);
+
+        impl<const C: usize, T, Ix: QueueIndex> Drop for Guard<'_, '_, C, T, Ix> {
+            fn drop(&mut self) {
+                self.0.close_gap();
+            }
+        }
+
+        let g = Guard(self);
+        let (head, tail) = g.0.queue.raw_slices(g.0.front..g.0.back);
+        g.0.front = g.0.back;
+        // Safety: The slices hold the values that have not
+        // been yielded, which are no longer part of the queue.
+        unsafe {
+            ptr::drop_in_place(head);
+            ptr::drop_in_place(tail);
+        }
+    }
+}
+
+/// Iterator removing values matching a predicate from a
+/// [Queue], from front to back. Created by
+/// [Queue::extract_if].
+pub struct ExtractIf<'a, const C: usize, T, F, Ix: QueueIndex = usize> {
+    queue: &'a mut Queue<C, T, Ix>,
+    // Original queue length.
+    len: usize,
+    // Number of values visited and removed.
+    processed: usize,
+    deleted: usize,
+    f: F,
+}
+
+impl<const C: usize, T, F, Ix: QueueIndex> Iterator for ExtractIf<'_, C, T, F, Ix>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while self.processed < self.len {
+            let slot = self.queue.slot(self.processed);
+            let value = self.queue.values[slot].as_mut_ptr();
+            // Safety: Logical positions between processed and
+            // len hold values that have not been moved.
+            let matched = (self.f)(unsafe { &mut *value });
+            self.processed += 1;
+            if matched {
+                self.deleted += 1;
+                // Safety: As above. The value is no longer
+                // part of the queue.
+                return Some(unsafe { ptr::read(value) });
+            }
+            if self.deleted > 0 {
+                let i = self.processed - 1;
+                self.queue.shift(i, i - self.deleted);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.len - self.processed))
+    }
+}
+
+impl<const C: usize, T, F, Ix: QueueIndex> Drop for ExtractIf<'_, C, T, F, Ix> {
+    fn drop(&mut self) {
+        if self.deleted > 0 {
+            for i in self.processed..self.len {
+                self.queue.shift(i, i - self.deleted);
+            }
+        }
+        self.queue.len = to_index(self.len - self.deleted);
+    }
+}
+
+/// Test value that counts its drops.
+#[cfg(test)]
+#[derive(Debug)]
+struct Counted<'a>(usize, &'a core::cell::Cell<usize>);
+
+#[cfg(test)]
+impl Drop for Counted<'_> {
+    fn drop(&mut self) {
+        self.1.set(self.1.get() + 1);
+    }
+}
+
+#[cfg(test)]
+impl Clone for Counted<'_> {
+    fn clone(&self) -> Self {
+        Counted(self.0, self.1)
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.0 = source.0;
+    }
+}
+
+#[cfg(test)]
+impl PartialEq for Counted<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+/// Replace `q` with a queue of [Counted] `values` whose front
+/// is at physical index `offset`, so that it wraps if the
+/// values do not fit after it, then reset `drops`.
+#[cfg(test)]
+fn refill<'a, const C: usize>(
+    q: &mut Queue<C, Counted<'a>>,
+    drops: &'a core::cell::Cell<usize>,
+    offset: usize,
+    values: impl IntoIterator<Item = usize>,
+) {
+    assert!(offset < C);
+    *q = Queue::new();
+    q.start = to_index(offset);
+    for v in values {
+        q.insert(Counted(v, drops)).unwrap();
+    }
+    drops.set(0);
+}
+
+#[test]
+fn test_queue() {
+    #[derive(Debug, PartialEq, Eq)]
+    struct S(usize);
+
+    let mut q: Queue<3, S> = Queue::default();
+    assert!(q.is_empty());
+    for i in 0..3 {
+        q.insert(S(i)).unwrap();
+    }
+    assert_eq!(3, q.len());
+    let ovf = q.insert(S(3));
+    assert_eq!(Err(InsertError(S(3))), ovf);
+    assert!(matches!(q.extract(), Some(S(0))));
+    q.insert(S(3)).unwrap();
+    assert_eq!(3, q.len());
+    for i in 1..=3 {
+        assert_eq!(Some(S(i)), q.extract());
+    }
+    assert!(q.extract().is_none());
+    assert!(q.is_empty());
+}
+
+#[test]
+fn test_front() {
+    let mut q: Queue<3, usize> = Queue::default();
+    assert!(q.front().is_none());
+    for i in 0..3 {
+        q.insert(i).unwrap();
+    }
+    assert_eq!(Some(&0), q.front());
+    // Move the front past the wrap point.
+    for i in 3..5 {
+        assert_eq!(Some(i - 3), q.extract());
+        q.insert(i).unwrap();
+    }
+    assert_eq!(Some(&2), q.front());
+    assert_eq!(Some(2), q.extract());
+    assert_eq!(Some(3), q.extract());
+    assert_eq!(Some(&4), q.front());
+    assert_eq!(1, q.len());
+    assert_eq!(Some(4), q.extract());
+    assert!(q.front().is_none());
+}
+
+#[test]
+fn test_front_mut() {
+    let mut q: Queue<3, usize> = Queue::default();
+    assert!(q.front_mut().is_none());
+    q.insert(0).unwrap();
+    q.insert(1).unwrap();
+    *q.front_mut().unwrap() = 10;
+    assert_eq!(Some(10), q.extract());
+    // Wrap the queue so the front sits at the last slot,
+    // then at the first.
+    q.insert(2).unwrap();
+    q.insert(3).unwrap();
+    assert_eq!(Some(1), q.extract());
+    *q.front_mut().unwrap() += 10;
+    assert_eq!(Some(12), q.extract());
+    *q.front_mut().unwrap() += 10;
+    assert_eq!(Some(13), q.extract());
+    assert!(q.front_mut().is_none());
+}
+
+#[test]
+fn test_back() {
+    let mut q: Queue<3, usize> = Queue::default();
+    assert!(q.back().is_none());
+    for i in 0..3 {
+        q.insert(i).unwrap();
+        assert_eq!(Some(&i), q.back());
+    }
+    // Insert past the wrap point.
+    for i in 3..8 {
+        q.extract().unwrap();
+        q.insert(i).unwrap();
+        assert_eq!(Some(&i), q.back());
+    }
+    assert_eq!(Some(5), q.extract());
+    assert_eq!(Some(6), q.extract());
+    assert_eq!(Some(&7), q.back());
+    assert_eq!(Some(7), q.extract());
+    assert!(q.back().is_none());
+}
+
+#[test]
+fn test_back_mut() {
+    let mut q: Queue<3, String> = Queue::default();
+    assert!(q.back_mut().is_none());
+    q.insert("a".to_string()).unwrap();
+    q.back_mut().unwrap().push('b');
+    assert_eq!(Some(&"ab".to_string()), q.front());
+    // Wrap the tail around to the first slot.
+    q.insert("c".to_string()).unwrap();
+    q.insert("d".to_string()).unwrap();
+    assert_eq!(Some("ab".to_string()), q.extract());
+    q.insert("e".to_string()).unwrap();
+    q.back_mut().unwrap().push('f');
+    for s in ["c", "d", "ef"] {
+        assert_eq!(Some(s.to_string()), q.extract());
+    }
+    assert!(q.back_mut().is_none());
+}
+
+#[test]
+fn test_get() {
+    let mut q: Queue<4, usize> = Queue::default();
+    assert!(q.get(0).is_none());
+    for i in 0..4 {
+        q.insert(i).unwrap();
+    }
+    // Make the logical range straddle the wrap point.
+    for i in 4..7 {
+        q.extract().unwrap();
+        q.insert(i).unwrap();
+    }
+    for i in 0..4 {
+        assert_eq!(Some(&(i + 3)), q.get(i));
+    }
+    assert!(q.get(4).is_none());
+    assert_eq!(q.front(), q.get(0));
+    assert_eq!(q.back(), q.get(3));
+}
+
+#[test]
+fn test_get_mut() {
+    let mut q: Queue<4, usize> = Queue::default();
+    assert!(q.get_mut(0).is_none());
+    for i in 0..4 {
+        q.insert(i).unwrap();
+    }
+    for i in 4..7 {
+        q.extract().unwrap();
+        q.insert(i).unwrap();
+    }
+    // Logical positions 1 and 2 are in the wrapped region.
+    *q.get_mut(1).unwrap() += 10;
+    *q.get_mut(2).unwrap() += 20;
+    assert!(q.get_mut(4).is_none());
+    for v in [3, 14, 25, 6] {
+        assert_eq!(Some(v), q.extract());
+    }
+}
+
+#[test]
+fn test_contains() {
+    let mut q: Queue<4, usize> = Queue::default();
+    assert!(!q.contains(&0));
+    for i in 0..4 {
+        q.insert(i).unwrap();
+    }
+    q.extract().unwrap();
+    q.extract().unwrap();
+    q.insert(4).unwrap();
+    q.insert(5).unwrap();
+    // 2 and 3 are at the end of the array, 4 and 5 at the
+    // start.
+    for i in 2..6 {
+        assert!(q.contains(&i));
+    }
+    assert!(!q.contains(&0));
+    assert!(!q.contains(&1));
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_remaining_capacity(ops in proptest::collection::vec(proptest::bool::ANY, 0..64)) {
+        let mut q: Queue<5, usize> = Queue::default();
+        for (i, insert) in ops.into_iter().enumerate() {
+            if insert {
+                let _ = q.insert(i);
+            } else {
+                q.extract();
+            }
+            proptest::prop_assert_eq!(q.capacity(), q.len() + q.remaining_capacity());
+        }
+    }
+}
+
+#[test]
+fn test_as_slices() {
+    let mut q: Queue<4, usize> = Queue::default();
+    let empty: &[usize] = &[];
+    assert_eq!((empty, empty), q.as_slices());
+    q.insert(0).unwrap();
+    q.insert(1).unwrap();
+    assert_eq!((&[0, 1][..], empty), q.as_slices());
+    q.insert(2).unwrap();
+    q.insert(3).unwrap();
+    assert_eq!((&[0, 1, 2, 3][..], empty), q.as_slices());
+    q.extract().unwrap();
+    q.insert(4).unwrap();
+    assert_eq!((&[1, 2, 3][..], &[4][..]), q.as_slices());
+    for _ in 0..3 {
+        q.extract().unwrap();
+    }
+    assert_eq!((&[4][..], empty), q.as_slices());
+}
+
+#[test]
+fn test_as_mut_slices() {
+    let mut q: Queue<4, usize> = Queue::default();
+    for i in 0..4 {
+        q.insert(i).unwrap();
+    }
+    q.extract().unwrap();
+    q.extract().unwrap();
+    q.insert(4).unwrap();
+    q.insert(5).unwrap();
+    let (head, tail) = q.as_mut_slices();
+    assert_eq!(2, head.len());
+    assert_eq!(2, tail.len());
+    for v in head.iter_mut().chain(tail.iter_mut()) {
+        *v *= 10;
+    }
+    for v in [20, 30, 40, 50] {
+        assert_eq!(Some(v), q.extract());
+    }
+    let (head, tail) = q.as_mut_slices();
+    assert!(head.is_empty() && tail.is_empty());
+}
+
+#[test]
+fn test_make_contiguous() {
+    let mut q: Queue<5, String> = Queue::default();
+    // Partially-filled and wrapped.
+    for i in 0..5 {
+        q.insert(i.to_string()).unwrap();
+    }
+    for i in 0..4 {
+        assert_eq!(Some(i.to_string()), q.extract());
+    }
+    q.insert("5".to_string()).unwrap();
+    q.insert("6".to_string()).unwrap();
+    assert_eq!(["4", "5", "6"], q.make_contiguous());
+    // Already contiguous.
+    assert_eq!(["4", "5", "6"], q.make_contiguous());
+    // Fully wrapped.
+    q.insert("7".to_string()).unwrap();
+    q.insert("8".to_string()).unwrap();
+    assert!(q.is_full());
+    q.extract().unwrap();
+    q.insert("9".to_string()).unwrap();
+    assert_eq!(["5", "6", "7", "8", "9"], q.make_contiguous());
+    assert!(q.as_slices().1.is_empty());
+    for i in 5..8 {
+        assert_eq!(Some(i.to_string()), q.extract());
+    }
+    q.insert("10".to_string()).unwrap();
+    for i in 8..11 {
+        assert_eq!(Some(i.to_string()), q.extract());
+    }
+    assert!(q.is_empty());
+}
+
+#[test]
+fn test_index() {
+    let mut q: Queue<3, usize> = Queue::default();
+    for i in 0..3 {
+        q.insert(i).unwrap();
+    }
+    q.extract().unwrap();
+    q.insert(3).unwrap();
+    for i in 0..3 {
+        assert_eq!(i + 1, q[i]);
+    }
+    q[2] += 10;
+    assert_eq!(13, q[2]);
+    assert_eq!(Some(&13), q.back());
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 2 but the index is 2")]
+fn test_index_out_of_bounds() {
+    let mut q: Queue<3, usize> = Queue::default();
+    q.insert(0).unwrap();
+    q.insert(1).unwrap();
+    let _ = q[2];
+}
+
+#[test]
+fn test_spare_capacity_mut() {
+    let mut q: Queue<5, String> = Queue::default();
+    let (head, tail) = q.spare_capacity_mut();
+    assert_eq!((5, 0), (head.len(), tail.len()));
+    for i in 0..4 {
+        q.insert(i.to_string()).unwrap();
+    }
+    q.extract().unwrap();
+    q.extract().unwrap();
+    // Free space is the last slot plus the first two.
+    let (head, tail) = q.spare_capacity_mut();
+    assert_eq!((1, 2), (head.len(), tail.len()));
+    head[0].write("4".to_string());
+    tail[0].write("5".to_string());
+    unsafe { q.commit(2) };
+    // Free space is now the one slot before the front.
+    let (head, tail) = q.spare_capacity_mut();
+    assert_eq!((1, 0), (head.len(), tail.len()));
+    head[0].write("6".to_string());
+    unsafe { q.commit(1) };
+    assert!(q.is_full());
+    let (head, tail) = q.spare_capacity_mut();
+    assert!(head.is_empty() && tail.is_empty());
+    for i in 2..7 {
+        assert_eq!(Some(i.to_string()), q.extract());
+    }
+}
+
+#[test]
+fn test_iter() {
+    let mut q: Queue<4, usize> = Queue::default();
+    assert_eq!(0, q.iter().count());
+    for i in 0..4 {
+        q.insert(i).unwrap();
+    }
+    q.extract().unwrap();
+    q.extract().unwrap();
+    q.insert(4).unwrap();
+    q.insert(5).unwrap();
+    let mut it = q.iter();
+    assert_eq!(4, it.len());
+    for i in 2..6 {
+        assert_eq!(Some(&i), it.next());
+    }
+    assert!(it.next().is_none());
+    let mut it = q.iter();
+    assert_eq!(Some(&5), it.next_back());
+    assert_eq!(Some(&2), it.next());
+    assert_eq!(Some(&4), it.next_back());
+    assert_eq!(Some(&3), it.next_back());
+    assert!(it.next().is_none());
+    assert!(it.next_back().is_none());
+}
+
+// IterMut hands out references through a raw pointer; run
+// this under `cargo miri test test_iter_mut` to check that
+// they do not alias.
+#[test]
+fn test_iter_mut() {
+    let mut q: Queue<4, String> = Queue::default();
+    assert_eq!(0, q.iter_mut().count());
+    for i in 0..4 {
+        q.insert(i.to_string()).unwrap();
+    }
+    q.extract().unwrap();
+    q.extract().unwrap();
+    q.insert("4".to_string()).unwrap();
+    q.insert("5".to_string()).unwrap();
+    // Hold all the references at once.
+    let refs: Vec<&mut String> = q.iter_mut().collect();
+    for r in refs {
+        r.push('!');
+    }
+    let mut it = q.iter_mut();
+    it.next_back().unwrap().push('?');
+    assert_eq!(3, it.len());
+    for s in ["2!", "3!", "4!", "5!?"] {
+        assert_eq!(Some(s.to_string()), q.extract());
+    }
+
+    let mut q: Queue<3, u32, u8> = Queue::from([1, 2, 3]);
+    q.extract().unwrap();
+    q.insert(4).unwrap();
+    let refs: Vec<&mut u32> = q.iter_mut().collect();
+    for r in refs {
+        *r *= 10;
+    }
+    assert_eq!(q, [20, 30, 40]);
+}
+
+#[test]
+fn test_range() {
+    let mut q: Queue<5, usize> = Queue::default();
+    for i in 0..5 {
+        q.insert(i).unwrap();
+    }
+    for i in 5..8 {
+        q.extract().unwrap();
+        q.insert(i).unwrap();
+    }
+    // Contents are 3..8, wrapping after 4.
+    let collect = |it: Iter<'_, 5, usize>| it.copied().collect::<Vec<_>>();
+    assert_eq!(vec![3, 4, 5, 6, 7], collect(q.range(..)));
+    assert_eq!(vec![4, 5, 6, 7], collect(q.range(1..)));
+    assert_eq!(vec![3, 4, 5], collect(q.range(..3)));
+    assert_eq!(vec![4, 5, 6], collect(q.range(1..=3)));
+    assert_eq!(vec![3, 4], collect(q.range(..=1)));
+    assert!(collect(q.range(5..5)).is_empty());
+    for v in q.range_mut(1..4) {
+        *v *= 10;
+    }
+    assert_eq!(vec![3, 40, 50, 60, 7], collect(q.iter()));
+    assert_eq!(0, q.range_mut(5..).count());
+}
+
+#[test]
+#[should_panic(expected = "range end 4 out of bounds for queue of length 3")]
+fn test_range_out_of_bounds() {
+    let mut q: Queue<5, usize> = Queue::default();
+    for i in 0..3 {
+        q.insert(i).unwrap();
+    }
+    q.range(1..4);
+}
+
+#[test]
+fn test_copy_to_slice() {
+    let mut q: Queue<4, u8> = Queue::default();
+    let mut buf = [0; 6];
+    assert_eq!(0, q.copy_to_slice(&mut buf));
+    for i in 0..4 {
+        q.insert(i).unwrap();
+    }
+    for i in 4..7 {
+        q.extract().unwrap();
+        q.insert(i).unwrap();
+    }
+    // Destination longer than the queue.
+    assert_eq!(4, q.copy_to_slice(&mut buf));
+    assert_eq!([3, 4, 5, 6, 0, 0], buf);
+    // Destination shorter than the queue, ending in the
+    // wrapped part.
+    let mut buf = [0; 3];
+    assert_eq!(3, q.copy_to_slice(&mut buf));
+    assert_eq!([3, 4, 5], buf);
+    // Destination shorter than the head slice.
+    q.extract().unwrap();
+    q.insert(7).unwrap();
+    let mut buf = [0; 1];
+    assert_eq!(1, q.copy_to_slice(&mut buf));
+    assert_eq!([4], buf);
+    assert_eq!(4, q.len());
+}
+
+#[test]
+fn test_as_contiguous() {
+    let mut q: Queue<3, usize> = Queue::default();
+    assert_eq!(Some(&[][..]), q.as_contiguous());
+    for i in 0..3 {
+        q.insert(i).unwrap();
+    }
+    q.extract().unwrap();
+    // start + len == C: still contiguous.
+    assert_eq!(Some(&[1, 2][..]), q.as_contiguous());
+    q.insert(3).unwrap();
+    // start + len == C + 1: wrapped.
+    assert!(!q.is_contiguous());
+    assert!(q.as_contiguous().is_none());
+    q.extract().unwrap();
+    q.extract().unwrap();
+    assert_eq!(Some(&[3][..]), q.as_contiguous());
+}
+
+#[test]
+fn test_clear() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<4, Counted> = Queue::default();
+    q.clear();
+    for i in 0..4 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    q.extract().unwrap();
+    q.extract().unwrap();
+    q.insert(Counted(4, &drops)).unwrap();
+    q.insert(Counted(5, &drops)).unwrap();
+    assert_eq!(2, drops.get());
+    q.clear();
+    assert_eq!(6, drops.get());
+    assert!(q.is_empty());
+    q.insert(Counted(6, &drops)).unwrap();
+    assert_eq!(Some(6), q.extract().map(|c| c.0));
+    drop(q);
+    assert_eq!(7, drops.get());
+}
+
+#[test]
+fn test_truncate() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<4, Counted> = Queue::default();
+    for i in 0..4 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    q.extract().unwrap();
+    q.extract().unwrap();
+    q.insert(Counted(4, &drops)).unwrap();
+    q.insert(Counted(5, &drops)).unwrap();
+    assert_eq!(2, drops.get());
+    q.truncate(4);
+    assert_eq!(2, drops.get());
+    // Drop the wrapped part plus one.
+    q.truncate(1);
+    assert_eq!(5, drops.get());
+    assert_eq!(Some(2), q.back().map(|c| c.0));
+    q.truncate(0);
+    assert_eq!(6, drops.get());
+    assert!(q.is_empty());
+    for i in 6..10 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    for i in 6..10 {
+        assert_eq!(Some(i), q.extract().map(|c| c.0));
+    }
+    assert_eq!(10, drops.get());
+}
+
+#[test]
+fn test_drop_front() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<4, Counted> = Queue::default();
+    assert_eq!(0, q.drop_front(1));
+    for i in 0..4 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    assert_eq!(3, q.drop_front(3));
+    assert_eq!(3, drops.get());
+    for i in 4..7 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    // Drop across the wrap point.
+    assert_eq!(2, q.drop_front(2));
+    assert_eq!(5, drops.get());
+    assert_eq!(Some(5), q.front().map(|c| c.0));
+    assert_eq!(2, q.drop_front(10));
+    assert_eq!(7, drops.get());
+    assert!(q.is_empty());
+    q.insert(Counted(7, &drops)).unwrap();
+    assert_eq!(Some(7), q.extract().map(|c| c.0));
+}
+
+#[test]
+fn test_retain() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<5, Counted> = Queue::default();
+    fn refill<'a>(q: &mut Queue<5, Counted<'a>>, drops: &'a core::cell::Cell<usize>) {
+        q.clear();
+        for i in 0..5 {
+            q.insert(Counted(i, drops)).unwrap();
+        }
+        q.drop_front(3);
+        for i in 5..8 {
+            q.insert(Counted(i, drops)).unwrap();
+        }
+    }
+    let values = |q: &Queue<5, Counted<'_>>| q.iter().map(|c| c.0).collect::<Vec<_>>();
+    // Wrapped queue holding 3..8.
+    refill(&mut q, &drops);
+    drops.set(0);
+    q.retain(|c| c.0 % 2 == 0);
+    assert_eq!(vec![4, 6], values(&q));
+    assert_eq!(3, drops.get());
+    refill(&mut q, &drops);
+    drops.set(0);
+    q.retain(|_| true);
+    assert_eq!(vec![3, 4, 5, 6, 7], values(&q));
+    assert_eq!(0, drops.get());
+    q.retain(|_| false);
+    assert!(q.is_empty());
+    assert_eq!(5, drops.get());
+    q.insert(Counted(8, &drops)).unwrap();
+    assert_eq!(vec![8], values(&q));
+}
+
+#[test]
+fn test_retain_panic() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<5, Counted> = Queue::default();
+    for i in 0..5 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    q.drop_front(2);
+    for i in 5..7 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    drops.set(0);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        q.retain(|c| {
+            assert!(c.0 != 5);
+            c.0 == 3
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(2, drops.get());
+    assert_eq!(vec![3, 5, 6], q.iter().map(|c| c.0).collect::<Vec<_>>());
+    drop(q);
+    assert_eq!(5, drops.get());
+}
+
+#[test]
+fn test_retain_mut() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<5, Counted> = Queue::default();
+    for i in 0..5 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    q.drop_front(3);
+    for i in 5..8 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    drops.set(0);
+    // Wrapped queue holding 3..8.
+    q.retain_mut(|c| {
+        c.0 *= 10;
+        c.0 != 40 && c.0 != 70
+    });
+    assert_eq!(2, drops.get());
+    assert_eq!(vec![30, 50, 60], q.iter().map(|c| c.0).collect::<Vec<_>>());
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        q.retain_mut(|c| {
+            assert!(c.0 != 60);
+            c.0 += 1;
+            c.0 != 31
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(3, drops.get());
+    assert_eq!(vec![51, 60], q.iter().map(|c| c.0).collect::<Vec<_>>());
+    q.retain_mut(|_| false);
+    assert_eq!(5, drops.get());
+    assert!(q.is_empty());
+}
+
+#[test]
+fn test_drain() {
+    let drops = core::cell::Cell::new(0);
+    let values = |q: &Queue<6, Counted<'_>>| q.iter().map(|c| c.0).collect::<Vec<_>>();
+    let mut q: Queue<6, Counted> = Queue::default();
+    // Wrapped queue holding 4..10. Drain near the front, so
+    // the front is moved.
+    refill(&mut q, &drops, 4, 4..10);
+    let mut d = q.drain(1..3);
+    assert_eq!(Some(5), d.next().map(|c| c.0));
+    drop(d);
+    assert_eq!(2, drops.get());
+    assert_eq!(vec![4, 7, 8, 9], values(&q));
+    // Drain near the back, so the back is moved.
+    refill(&mut q, &drops, 4, 4..10);
+    drop(q.drain(3..5));
+    assert_eq!(2, drops.get());
+    assert_eq!(vec![4, 5, 6, 9], values(&q));
+    q.insert(Counted(10, &drops)).unwrap();
+    assert_eq!(vec![4, 5, 6, 9, 10], values(&q));
+    // Drain from both ends.
+    refill(&mut q, &drops, 4, 4..10);
+    let mut d = q.drain(..);
+    assert_eq!(6, d.len());
+    assert_eq!(Some(9), d.next_back().map(|c| c.0));
+    assert_eq!(Some(4), d.next().map(|c| c.0));
+    drop(d);
+    assert_eq!(6, drops.get());
+    assert!(q.is_empty());
+    // Empty drain.
+    refill(&mut q, &drops, 4, 4..10);
+    assert_eq!(0, q.drain(6..).count());
+    assert_eq!(vec![4, 5, 6, 7, 8, 9], values(&q));
+    assert_eq!(0, drops.get());
+}
+
+#[test]
+fn test_extract_if() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<6, Counted> = Queue::default();
+    for i in 0..6 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    q.drop_front(4);
+    for i in 6..10 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    drops.set(0);
+    // Wrapped queue holding 4..10.
+    let removed: Vec<usize> = q.extract_if(|c| c.0 % 3 != 0).map(|c| c.0).collect();
+    assert_eq!(vec![4, 5, 7, 8], removed);
+    assert_eq!(4, drops.get());
+    assert_eq!(vec![6, 9], q.iter().map(|c| c.0).collect::<Vec<_>>());
+    for i in 10..14 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    // Stop early: unvisited values stay.
+    let mut it = q.extract_if(|c| c.0 > 9);
+    assert_eq!(Some(10), it.next().map(|c| c.0));
+    drop(it);
+    assert_eq!(
+        vec![6, 9, 11, 12, 13],
+        q.iter().map(|c| c.0).collect::<Vec<_>>()
+    );
+    assert_eq!(5, drops.get());
+    assert_eq!(0, q.extract_if(|_| false).count());
+    assert_eq!(5, q.len());
+}
+
+#[test]
+fn test_force_insert() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<3, Counted> = Queue::default();
+    for i in 0..3 {
+        assert!(q.force_insert(Counted(i, &drops)).is_none());
+    }
+    for i in 3..8 {
+        let evicted = q.force_insert(Counted(i, &drops)).unwrap();
+        assert_eq!(i - 3, evicted.0);
+    }
+    assert_eq!(5, drops.get());
+    assert_eq!(3, q.len());
+    assert_eq!(Some(5), q.extract().map(|c| c.0));
+    assert!(q.force_insert(Counted(8, &drops)).is_none());
+    for i in 6..9 {
+        assert_eq!(Some(i), q.extract().map(|c| c.0));
+    }
+    assert_eq!(9, drops.get());
+}
+
+#[test]
+fn test_insert_error() {
+    let mut q: Queue<1, String> = Queue::default();
+    q.insert("a".to_string()).unwrap();
+    let s = "rejected".to_string();
+    let ptr = s.as_ptr();
+    let err = q.insert(s).unwrap_err();
+    let s = err.into_inner();
+    // The very same allocation comes back.
+    assert_eq!(ptr, s.as_ptr());
+    assert_eq!("rejected", s);
+    let err: QueueError = q.insert(s).unwrap_err().into();
+    assert!(matches!(err, QueueError::Overflow));
+}
+
+#[test]
+fn test_push_front() {
+    let mut q: Queue<3, usize> = Queue::default();
+    // start is 0, so it wraps to the last slot.
+    q.push_front(1).unwrap();
+    q.insert(2).unwrap();
+    q.push_front(0).unwrap();
+    assert_eq!(Err(InsertError(5)), q.push_front(5));
+    assert_eq!(Some(0), q.extract());
+    assert_eq!(Some(1), q.extract());
+    q.insert(3).unwrap();
+    q.push_front(1).unwrap();
+    for i in 1..4 {
+        assert_eq!(Some(i), q.extract());
+    }
+    assert!(q.is_empty());
+    q.push_front(4).unwrap();
+    assert_eq!(Some(&4), q.back());
+    assert_eq!(Some(4), q.extract());
+}
+
+#[test]
+fn test_pop_back() {
+    let mut q: Queue<3, usize> = Queue::default();
+    assert!(q.pop_back().is_none());
+    q.insert(0).unwrap();
+    assert_eq!(Some(0), q.pop_back());
+    assert!(q.is_empty());
+    // Wrap the back around to the first slot.
+    for i in 1..4 {
+        q.insert(i).unwrap();
+    }
+    q.extract().unwrap();
+    q.insert(4).unwrap();
+    assert_eq!(Some(4), q.pop_back());
+    assert_eq!(Some(3), q.pop_back());
+    for i in 5..9 {
+        q.insert(i).unwrap();
+        assert_eq!(Some(i), q.pop_back());
+    }
+    assert_eq!(Some(&2), q.front());
+    q.insert(9).unwrap();
+    assert_eq!(Some(2), q.extract());
+    assert_eq!(Some(9), q.extract());
+}
+
+#[test]
+fn test_remove() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<5, Counted> = Queue::default();
+    for i in 0..5 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    q.drop_front(3);
+    for i in 5..8 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    drops.set(0);
+    // Wrapped queue holding 3..8.
+    assert_eq!(Some(4), q.remove(1).map(|c| c.0));
+    assert_eq!(Some(6), q.remove(2).map(|c| c.0));
+    assert!(q.remove(3).is_none());
+    assert_eq!(2, drops.get());
+    assert_eq!(vec![3, 5, 7], q.iter().map(|c| c.0).collect::<Vec<_>>());
+    drop(q);
+    assert_eq!(5, drops.get());
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_remove_model(
+        ops in proptest::collection::vec((0..3u8, 0..6usize), 0..64),
+    ) {
+        let mut q: Queue<5, usize> = Queue::default();
+        let mut model = std::collections::VecDeque::new();
+        for (i, (op, index)) in ops.into_iter().enumerate() {
+            match op {
+                0 => {
+                    if q.insert(i).is_ok() {
+                        model.push_back(i);
+                    }
+                }
+                1 => proptest::prop_assert_eq!(model.pop_front(), q.extract()),
+                _ => proptest::prop_assert_eq!(model.remove(index), q.remove(index)),
+            }
+            proptest::prop_assert!(q.iter().eq(model.iter()));
+        }
+    }
+}
+
+#[test]
+fn test_insert_at() {
+    let mut q: Queue<6, usize> = Queue::default();
+    q.insert_at(0, 3).unwrap();
+    q.insert_at(0, 1).unwrap();
+    q.insert_at(2, 5).unwrap();
+    assert_eq!(vec![1, 3, 5], q.iter().copied().collect::<Vec<_>>());
+    // Wrap, then insert near the front and near the back.
+    q.drop_front(1);
+    q.insert(7).unwrap();
+    q.push_front(1).unwrap();
+    q.insert_at(1, 2).unwrap();
+    q.insert_at(5, 8).unwrap();
+    assert_eq!(Err(InsertError(4)), q.insert_at(2, 4));
+    assert_eq!(
+        vec![1, 2, 3, 5, 7, 8],
+        q.iter().copied().collect::<Vec<_>>()
+    );
+    q.remove(3).unwrap();
+    q.insert_at(3, 6).unwrap();
+    assert_eq!(
+        vec![1, 2, 3, 6, 7, 8],
+        q.iter().copied().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+#[should_panic(expected = "insertion index 2 out of bounds for queue of length 1")]
+fn test_insert_at_out_of_bounds() {
+    let mut q: Queue<3, usize> = Queue::default();
+    q.insert(0).unwrap();
+    let _ = q.insert_at(2, 1);
+}
+
+#[test]
+fn test_swap() {
+    let mut q: Queue<4, usize> = Queue::default();
+    for i in 0..4 {
+        q.insert(i).unwrap();
+    }
+    q.drop_front(2);
+    q.insert(4).unwrap();
+    q.insert(5).unwrap();
+    // Wrapped queue holding 2..6; positions 0 and 3 are on
+    // opposite sides of the wrap point.
+    q.swap(0, 3);
+    q.swap(1, 1);
+    assert_eq!(vec![5, 3, 4, 2], q.iter().copied().collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 2 but the index is 2")]
+fn test_swap_out_of_bounds() {
+    let mut q: Queue<3, usize> = Queue::default();
+    q.insert(0).unwrap();
+    q.insert(1).unwrap();
+    q.swap(0, 2);
+}
+
+#[test]
+fn test_swap_remove_front() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<4, Counted> = Queue::default();
+    assert!(q.swap_remove_front(0).is_none());
+    for i in 0..4 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    q.drop_front(3);
+    for i in 4..7 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    drops.set(0);
+    // Wrapped queue holding 3..7; the front is in the last
+    // slot.
+    assert_eq!(Some(5), q.swap_remove_front(2).map(|c| c.0));
+    assert_eq!(vec![4, 3, 6], q.iter().map(|c| c.0).collect::<Vec<_>>());
+    assert_eq!(Some(4), q.swap_remove_front(0).map(|c| c.0));
+    assert!(q.swap_remove_front(2).is_none());
+    assert_eq!(2, drops.get());
+    drop(q);
+    assert_eq!(4, drops.get());
+}
+
+#[test]
+fn test_swap_remove_back() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<4, Counted> = Queue::default();
+    assert!(q.swap_remove_back(0).is_none());
+    for i in 0..4 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    q.drop_front(3);
+    for i in 4..7 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    drops.set(0);
+    // Wrapped queue holding 3..7; the back is in the third
+    // slot.
+    assert_eq!(Some(3), q.swap_remove_back(0).map(|c| c.0));
+    assert_eq!(vec![6, 4, 5], q.iter().map(|c| c.0).collect::<Vec<_>>());
+    assert_eq!(Some(5), q.swap_remove_back(2).map(|c| c.0));
+    assert!(q.swap_remove_back(2).is_none());
+    assert_eq!(vec![6, 4], q.iter().map(|c| c.0).collect::<Vec<_>>());
+    assert_eq!(2, drops.get());
+    drop(q);
+    assert_eq!(4, drops.get());
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_rotate_model(
+        ops in proptest::collection::vec((0..4u8, 0..6usize), 0..64),
+    ) {
+        let mut q: Queue<5, usize> = Queue::default();
+        let mut model = std::collections::VecDeque::new();
+        for (i, (op, n)) in ops.into_iter().enumerate() {
+            let n = n.min(model.len());
+            match op {
+                0 => {
+                    if q.insert(i).is_ok() {
+                        model.push_back(i);
+                    }
+                }
+                1 => proptest::prop_assert_eq!(model.pop_front(), q.extract()),
+                2 => {
+                    model.rotate_left(n);
+                    q.rotate_left(n);
+                }
+                _ => {
+                    model.rotate_right(n);
+                    q.rotate_right(n);
+                }
+            }
+            proptest::prop_assert!(q.iter().eq(model.iter()));
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "rotation 3 out of bounds for queue of length 2")]
+fn test_rotate_out_of_bounds() {
+    let mut q: Queue<3, usize> = Queue::default();
+    q.insert(0).unwrap();
+    q.insert(1).unwrap();
+    q.rotate_left(3);
+}
+
+#[test]
+fn test_rotate_zero_capacity() {
+    let mut q: Queue<0, usize> = Queue::default();
+    q.rotate_left(0);
+    q.rotate_right(0);
+    assert!(q.is_empty());
+}
+
+#[test]
+fn test_append() {
+    let mut q: Queue<5, String> = Queue::default();
+    let mut r: Queue<3, String> = Queue::default();
+    for i in 0..5 {
+        q.insert(i.to_string()).unwrap();
+    }
+    q.drop_front(4);
+    for i in 5..7 {
+        r.insert(i.to_string()).unwrap();
+    }
+    // Wrapped destination.
+    q.append(&mut r).unwrap();
+    assert!(r.is_empty());
+    for i in 7..10 {
+        r.insert(i.to_string()).unwrap();
+    }
+    // Too long: nothing changes.
+    assert!(matches!(q.append(&mut r), Err(QueueError::Overflow)));
+    assert_eq!(vec!["4", "5", "6"], q.iter().collect::<Vec<_>>());
+    assert_eq!(vec!["7", "8", "9"], r.iter().collect::<Vec<_>>());
+    r.extract().unwrap();
+    // Exact fit.
+    q.append(&mut r).unwrap();
+    assert!(q.is_full());
+    assert!(r.is_empty());
+    assert_eq!(vec!["4", "5", "6", "8", "9"], q.iter().collect::<Vec<_>>());
+    r.insert("10".to_string()).unwrap();
+    assert_eq!(Some(&"10".to_string()), r.front());
+}
+
+#[test]
+fn test_split_to() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<5, Counted> = Queue::default();
+    for i in 0..5 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    q.drop_front(3);
+    for i in 5..8 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    drops.set(0);
+    // Wrapped queue holding 3..8.
+    let mut r = q.split_to(3);
+    assert_eq!(0, drops.get());
+    assert_eq!(vec![3, 4, 5], r.iter().map(|c| c.0).collect::<Vec<_>>());
+    assert_eq!(vec![6, 7], q.iter().map(|c| c.0).collect::<Vec<_>>());
+    assert!(q.split_to(0).is_empty());
+    r.insert(Counted(8, &drops)).unwrap();
+    drop(r);
+    assert_eq!(4, drops.get());
+    let r = q.split_to(2);
+    assert!(q.is_empty());
+    drop(q);
+    drop(r);
+    assert_eq!(6, drops.get());
+}
+
+#[test]
+fn test_split_off_back() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<5, Counted> = Queue::default();
+    for i in 0..5 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    q.drop_front(3);
+    for i in 5..8 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    drops.set(0);
+    // Wrapped queue holding 3..8.
+    assert!(q.split_off_back(0).is_empty());
+    assert_eq!(5, q.len());
+    let r = q.split_off_back(3);
+    assert_eq!(vec![5, 6, 7], r.iter().map(|c| c.0).collect::<Vec<_>>());
+    assert_eq!(vec![3, 4], q.iter().map(|c| c.0).collect::<Vec<_>>());
+    let s = q.split_off_back(2);
+    assert!(q.is_empty());
+    assert_eq!(vec![3, 4], s.iter().map(|c| c.0).collect::<Vec<_>>());
+    assert_eq!(0, drops.get());
+    drop((q, r, s));
+    assert_eq!(5, drops.get());
+}
+
+#[test]
+fn test_extend_from_slice() {
+    let mut q: Queue<5, String> = Queue::default();
+    let src: Vec<String> = (0..5).map(|i| i.to_string()).collect();
+    // Exact fit.
+    q.extend_from_slice(&src).unwrap();
+    assert!(q.iter().eq(src.iter()));
+    q.drop_front(4);
+    // Overflow leaves the queue unchanged.
+    assert!(matches!(
+        q.extend_from_slice(&src),
+        Err(QueueError::Overflow)
+    ));
+    assert_eq!(vec!["4"], q.iter().collect::<Vec<_>>());
+    // Wrapped destination.
+    q.extend_from_slice(&src[..3]).unwrap();
+    q.extend_from_slice(&[]).unwrap();
+    assert_eq!(vec!["4", "0", "1", "2"], q.iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_copy_from_slice() {
+    let mut q: Queue<5, u8> = Queue::default();
+    q.copy_from_slice(&[]).unwrap();
+    assert!(q.is_empty());
+    q.copy_from_slice(&[0, 1, 2, 3]).unwrap();
+    q.drop_front(3);
+    assert!(matches!(
+        q.copy_from_slice(&[0; 5]),
+        Err(QueueError::Overflow)
+    ));
+    // Split across the wrap point.
+    q.copy_from_slice(&[4, 5, 6]).unwrap();
+    assert_eq!((&[3, 4][..], &[5, 6][..]), q.as_slices());
+    q.copy_from_slice(&[7]).unwrap();
+    assert!(q.is_full());
+    q.copy_from_slice(&[]).unwrap();
+    assert_eq!(vec![3, 4, 5, 6, 7], q.iter().copied().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_bulk_offsets() {
+    const C: usize = 5;
+
+    /// A queue holding `n` values counting up from 10,
+    /// starting at physical slot `start`.
+    fn make(start: usize, n: usize) -> Queue<C, u8> {
+        let mut q = Queue::new();
+        q.copy_from_slice(&[0; C][..start]).unwrap();
+        q.drop_front(start);
+        for i in 0..n {
+            q.insert(10 + i as u8).unwrap();
+        }
+        q
+    }
+
+    let expected: Vec<u8> = (10..10 + C as u8).collect();
+    for start in 0..C {
+        for n in 0..=C {
+            let mut q = make(start, 0);
+            q.copy_from_slice(&expected[..n]).unwrap();
+            assert_eq!(q, expected[..n]);
+
+            let mut q = make(start, n);
+            let mut buf = [0; C];
+            assert_eq!(n, q.extract_into(&mut buf));
+            assert_eq!(buf[..n], expected[..n]);
+            assert!(q.is_empty());
+
+            let mut q = make(start, C);
+            let mut buf = [0; C];
+            assert_eq!(n, q.extract_into(&mut buf[..n]));
+            assert_eq!(buf[..n], expected[..n]);
+            assert_eq!(q, expected[n..]);
+
+            let q: Queue<8, u8> = make(start, n).grow();
+            assert_eq!(q, expected[..n]);
+
+            let q: Queue<10, u8> = make(start, n).concat(make((start + 2) % C, C - n));
+            assert_eq!(q.len(), C);
+            assert_eq!(q.iter().take(n).copied().collect::<Vec<_>>(), expected[..n]);
+            assert_eq!(
+                q.iter().skip(n).copied().collect::<Vec<_>>(),
+                expected[..C - n]
+            );
+        }
+        let a = make(start, C).extract_array::<3>().unwrap();
+        assert_eq!(a, expected[..3]);
+        let a = make(start, C).into_array().unwrap();
+        assert_eq!(a, expected[..]);
+    }
+}
+
+#[test]
+fn test_insert_many() {
+    let mut q: Queue<4, usize> = Queue::default();
+    assert_eq!(2, q.insert_many([0, 1]));
+    let mut src = 2..10;
+    assert_eq!(2, q.insert_many(src.by_ref()));
+    assert_eq!(vec![0, 1, 2, 3], q.iter().copied().collect::<Vec<_>>());
+    // Already full: nothing is taken.
+    assert_eq!(0, q.insert_many(src.by_ref()));
+    assert_eq!(Some(4), src.next());
+    q.drop_front(3);
+    assert_eq!(3, q.insert_many(src.by_ref()));
+    assert_eq!(vec![3, 5, 6, 7], q.iter().copied().collect::<Vec<_>>());
+    assert_eq!(vec![8, 9], src.collect::<Vec<_>>());
+}
+
+#[test]
+fn test_extract_into() {
+    let mut q: Queue<5, u8> = Queue::default();
+    let mut buf = [0u8; 3];
+    assert_eq!(0, q.extract_into(&mut buf));
+    q.copy_from_slice(&[0, 1, 2, 3]).unwrap();
+    q.drop_front(3);
+    q.copy_from_slice(&[4, 5, 6]).unwrap();
+    // Wrapped source, partial fill of the queue.
+    assert_eq!(3, q.extract_into(&mut buf));
+    assert_eq!([3, 4, 5], buf);
+    // Partial fill of the destination.
+    assert_eq!(1, q.extract_into(&mut buf));
+    assert_eq!([6, 4, 5], buf);
+    assert!(q.is_empty());
+
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<5, Counted> = Queue::default();
+    for i in 0..5 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    q.drop_front(3);
+    for i in 5..8 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    let mut buf: Vec<Counted> = (10..14).map(|i| Counted(i, &drops)).collect();
+    drops.set(0);
+    assert_eq!(4, q.extract_into(&mut buf[..]));
+    assert_eq!(4, drops.get());
+    assert_eq!(
+        vec![3, 4, 5, 6],
+        buf.iter().map(|c| c.0).collect::<Vec<_>>()
+    );
+    assert_eq!(vec![7], q.iter().map(|c| c.0).collect::<Vec<_>>());
+    drop((q, buf));
+    assert_eq!(9, drops.get());
+}
+
+#[test]
+fn test_extract_array() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<4, Counted> = Queue::default();
+    assert!(q.extract_array::<1>().is_none());
+    assert!(q.extract_array::<0>().is_some());
+    for i in 0..4 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    // The whole capacity.
+    let a = q.extract_array::<4>().unwrap();
+    assert_eq!([0, 1, 2, 3], a.each_ref().map(|c| c.0));
+    assert!(q.is_empty());
+    drop(a);
+    assert_eq!(4, drops.get());
+    for i in 4..7 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    q.drop_front(2);
+    for i in 7..9 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    assert_eq!(6, drops.get());
+    // Straddling the wrap point.
+    assert!(q.extract_array::<4>().is_none());
+    let a = q.extract_array::<3>().unwrap();
+    assert_eq!([6, 7, 8], a.each_ref().map(|c| c.0));
+    assert!(q.is_empty());
+    assert_eq!(6, drops.get());
+    drop((a, q));
+    assert_eq!(9, drops.get());
+}
+
+#[test]
+fn test_fill() {
+    let mut q: Queue<4, String> = Queue::default();
+    q.insert("a".to_string()).unwrap();
+    q.insert("b".to_string()).unwrap();
+    q.drop_front(1);
+    q.fill("x".to_string());
+    assert_eq!(q.len(), q.capacity());
+    assert_eq!(vec!["b", "x", "x", "x"], q.iter().collect::<Vec<_>>());
+    // No-op when full.
+    q.fill("y".to_string());
+    q.fill_with(|| unreachable!());
+    assert_eq!(vec!["b", "x", "x", "x"], q.iter().collect::<Vec<_>>());
+    q.drop_front(3);
+    let mut n = 0;
+    q.fill_with(|| {
+        n += 1;
+        n.to_string()
+    });
+    assert_eq!(3, n);
+    assert_eq!(vec!["x", "1", "2", "3"], q.iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_resize_with() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<5, Counted> = Queue::default();
+    for i in 0..5 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    q.drop_front(3);
+    drops.set(0);
+    let mut next = 5;
+    let mut f = || {
+        next += 1;
+        Counted(next - 1, &drops)
+    };
+    // Grow into the wrapped region.
+    q.resize_with(5, &mut f).unwrap();
+    assert_eq!(
+        vec![3, 4, 5, 6, 7],
+        q.iter().map(|c| c.0).collect::<Vec<_>>()
+    );
+    assert!(matches!(
+        q.resize_with(6, &mut f),
+        Err(QueueError::Overflow)
+    ));
+    assert_eq!(5, q.len());
+    assert_eq!(0, drops.get());
+    // Shrink.
+    q.resize_with(2, &mut f).unwrap();
+    assert_eq!(vec![3, 4], q.iter().map(|c| c.0).collect::<Vec<_>>());
+    assert_eq!(3, drops.get());
+    q.resize_with(2, &mut f).unwrap();
+    assert_eq!(3, drops.get());
+    assert_eq!(2, q.len());
+}
+
+#[test]
+fn test_dedup() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<6, Counted> = Queue::default();
+    q.dedup();
+    let values = |q: &Queue<6, Counted<'_>>| q.iter().map(|c| c.0).collect::<Vec<_>>();
+    // All equal, wrapped.
+    refill(&mut q, &drops, 4, [1, 1, 1, 1, 1]);
+    q.dedup();
+    assert_eq!(vec![1], values(&q));
+    assert_eq!(4, drops.get());
+    // Alternating.
+    refill(&mut q, &drops, 4, [1, 2, 1, 2, 1, 2]);
+    q.dedup();
+    assert_eq!(vec![1, 2, 1, 2, 1, 2], values(&q));
+    assert_eq!(0, drops.get());
+    // Runs straddling the wrap point.
+    refill(&mut q, &drops, 4, [1, 2, 2, 3, 3, 4]);
+    q.dedup();
+    assert_eq!(vec![1, 2, 3, 4], values(&q));
+    assert_eq!(2, drops.get());
+}
+
+#[test]
+fn test_replace_front() {
+    let mut q: Queue<3, String> = Queue::default();
+    assert_eq!(Err("a".to_string()), q.replace_front("a".to_string()));
+    assert!(q.is_empty());
+    for s in ["a", "b", "c"] {
+        q.insert(s.to_string()).unwrap();
+    }
+    q.extract().unwrap();
+    q.insert("d".to_string()).unwrap();
+    assert_eq!(Ok("b".to_string()), q.replace_front("e".to_string()));
+    assert_eq!(3, q.len());
+    assert_eq!(vec!["e", "c", "d"], q.iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_map() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<4, Counted> = Queue::default();
+    for i in 0..4 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    q.drop_front(2);
+    for i in 4..6 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    drops.set(0);
+    let q = q.map(|c| Counted(c.0 * 10, c.1));
+    assert_eq!(4, drops.get());
+    assert_eq!(
+        vec![20, 30, 40, 50],
+        q.iter().map(|c| c.0).collect::<Vec<_>>()
+    );
+    // Panic halfway: two converted, one in flight, one not
+    // yet converted.
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        q.map(|c| {
+            assert!(c.0 != 40);
+            Counted(c.0 + 1, c.1)
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(10, drops.get());
+}
+
+#[test]
+fn test_insert_with() {
+    let mut calls = 0;
+    let mut q: Queue<2, usize> = Queue::default();
+    for i in 0..4 {
+        let _ = q.insert_with(|| {
+            calls += 1;
+            i
+        });
+    }
+    assert_eq!(2, calls);
+    assert_eq!(vec![0, 1], q.iter().copied().collect::<Vec<_>>());
+    q.extract().unwrap();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        q.insert_with(|| panic!("construction failed"))
+    }));
+    assert!(result.is_err());
+    assert_eq!(vec![1], q.iter().copied().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_pop_if() {
+    let mut q: Queue<3, usize> = Queue::default();
+    assert!(q.pop_if(|_| unreachable!()).is_none());
+    q.insert(1).unwrap();
+    q.insert(2).unwrap();
+    assert!(q.pop_if(|_| false).is_none());
+    assert_eq!(vec![1, 2], q.iter().copied().collect::<Vec<_>>());
+    assert!(q
+        .pop_if(|v| {
+            *v += 10;
+            false
+        })
+        .is_none());
+    assert_eq!(Some(&11), q.front());
+    assert_eq!(
+        Some(12),
+        q.pop_if(|v| {
+            *v += 1;
+            true
+        })
+    );
+    assert_eq!(vec![2], q.iter().copied().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_sort() {
+    let mut q: Queue<5, (usize, usize)> = Queue::default();
+    q.sort();
+    q.insert((1, 0)).unwrap();
+    q.sort();
+    assert_eq!(vec![(1, 0)], q.iter().copied().collect::<Vec<_>>());
+    q.copy_from_slice(&[(0, 0), (0, 0), (0, 0), (2, 1)])
+        .unwrap();
+    q.drop_front(4);
+    q.copy_from_slice(&[(1, 1), (0, 1), (2, 2), (1, 2)])
+        .unwrap();
+    // Wrapped; the sort must be stable.
+    q.sort_by_key(|v| v.0);
+    let expected = vec![(0, 1), (1, 1), (1, 2), (2, 1), (2, 2)];
+    assert_eq!(expected, q.iter().copied().collect::<Vec<_>>());
+    for v in expected {
+        assert_eq!(Some(v), q.extract());
+    }
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_sort_model(
+        values in proptest::collection::vec(0..10u8, 0..8),
+        rotation in 0..8usize,
+    ) {
+        let mut q: Queue<8, u8> = Queue::default();
+        q.resize_with(rotation, Default::default).unwrap();
+        q.drop_front(rotation);
+        q.copy_from_slice(&values).unwrap();
+        q.sort();
+        let mut model = values.clone();
+        model.sort();
+        proptest::prop_assert!(q.iter().eq(model.iter()));
+    }
+}
+
+#[test]
+fn test_binary_search() {
+    let mut q: Queue<6, usize> = Queue::default();
+    assert_eq!(Err(0), q.binary_search(&1));
+    assert_eq!(0, q.partition_point(|_| true));
+    q.copy_from_slice(&[0, 0, 0, 0]).unwrap();
+    q.drop_front(4);
+    // Wrapped: physically [3, 5, 7, _, 1, 3]
+    q.copy_from_slice(&[1, 3, 3, 5, 7]).unwrap();
+    assert!(!q.is_contiguous());
+    for (i, v) in [1, 3, 3, 5, 7].iter().enumerate() {
+        let found = q.binary_search(v).unwrap();
+        assert_eq!(*v, q[found]);
+        if *v != 3 {
+            assert_eq!(i, found);
+        }
+    }
+    assert!(matches!(q.binary_search(&3), Ok(1..=2)));
+    assert_eq!(Err(0), q.binary_search(&0));
+    assert_eq!(Err(1), q.binary_search(&2));
+    assert_eq!(Err(3), q.binary_search(&4));
+    assert_eq!(Err(5), q.binary_search(&8));
+    assert_eq!(Ok(3), q.binary_search_by_key(&10, |v| v * 2));
+    assert_eq!(1, q.partition_point(|&v| v < 3));
+    assert_eq!(3, q.partition_point(|&v| v <= 3));
+    assert_eq!(5, q.partition_point(|_| true));
+}
+
+#[test]
+fn test_reverse() {
+    let mut q: Queue<5, String> = Queue::default();
+    q.reverse();
+    assert!(q.is_empty());
+    q.insert("a".to_string()).unwrap();
+    q.reverse();
+    assert_eq!(Some("a"), q.front().map(String::as_str));
+    for v in ["b", "c", "d"] {
+        q.insert(v.to_string()).unwrap();
+    }
+    q.reverse();
+    assert!(q.iter().eq(["d", "c", "b", "a"].iter()));
+    q.drop_front(3);
+    for v in ["e", "f", "g", "h"] {
+        q.insert(v.to_string()).unwrap();
+    }
+    assert!(!q.is_contiguous());
+    q.reverse();
+    for v in ["h", "g", "f", "e", "a"] {
+        assert_eq!(Some(v.to_string()), q.extract());
+    }
+    assert!(q.is_empty());
+}
+
+#[test]
+fn test_clone_from() {
+    use core::cell::Cell;
+
+    let drops = Cell::new(0);
+    for dlen in 0..=4 {
+        for slen in 0..=4 {
+            let mut dst: Queue<4, Counted> = Queue::default();
+            refill(&mut dst, &drops, 3, 100..100 + dlen);
+            let mut src = Queue::default();
+            refill(&mut src, &drops, 0, 200..200 + slen);
+            let fresh = src.clone();
+            drops.set(0);
+            dst.clone_from(&src);
+            assert_eq!(dlen.saturating_sub(slen), drops.get());
+            assert!(dst.iter().eq(fresh.iter()));
+            assert_eq!(slen, dst.len());
+        }
+    }
+}
+
+#[test]
+fn test_clone() {
+    use core::cell::Cell;
+
+    #[derive(Debug, PartialEq)]
+    struct Fragile<'a>(Counted<'a>);
+
+    impl Clone for Fragile<'_> {
+        fn clone(&self) -> Self {
+            assert!(self.0 .0 != 3, "clone failed");
+            Fragile(self.0.clone())
+        }
+    }
+
+    let drops = Cell::new(0);
+    let mut q: Queue<4, Fragile> = Queue::default();
+    for i in 0..3 {
+        q.insert(Fragile(Counted(i, &drops))).unwrap();
+    }
+    q.drop_front(2);
+    for i in 3..6 {
+        q.insert(Fragile(Counted(i, &drops))).unwrap();
+    }
+    // Wrapped: 2, 3, 4, 5. Cloning 3 panics after 2 is cloned.
+    drops.set(0);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| q.clone()));
+    assert!(result.is_err());
+    assert_eq!(1, drops.get());
+    assert_eq!(4, q.len());
+
+    q.extract().unwrap();
+    q.extract().unwrap();
+    let mut r = q.clone();
+    assert!(r.is_contiguous());
+    assert!(r.iter().eq(q.iter()));
+    r.extract().unwrap();
+    r.insert(Fragile(Counted(6, &drops))).unwrap();
+    q.insert(Fragile(Counted(7, &drops))).unwrap();
+    assert_eq!(vec![4, 5, 7], q.iter().map(|v| v.0 .0).collect::<Vec<_>>());
+    assert_eq!(vec![5, 6], r.iter().map(|v| v.0 .0).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_debug() {
+    let mut q: Queue<4, &str> = Queue::default();
+    assert_eq!("[]", format!("{q:?}"));
+    q.copy_from_slice(&["x", "x", "x"]).unwrap();
+    q.drop_front(3);
+    q.copy_from_slice(&["a", "b", "c"]).unwrap();
+    assert!(!q.is_contiguous());
+    assert_eq!(r#"["a", "b", "c"]"#, format!("{q:?}"));
+    assert_eq!(
+        "[\n    \"a\",\n    \"b\",\n    \"c\",\n]",
+        format!("{q:#?}")
+    );
+}
+
+#[test]
+fn test_eq() {
+    let mut q: Queue<4, usize> = Queue::default();
+    let mut r: Queue<4, usize> = Queue::default();
+    let mut s: Queue<8, usize> = Queue::default();
+    assert_eq!(q, r);
+    assert_eq!(q, s);
+    q.copy_from_slice(&[0, 0, 0]).unwrap();
+    q.drop_front(3);
+    q.copy_from_slice(&[1, 2, 3]).unwrap();
+    assert!(!q.is_contiguous());
+    r.copy_from_slice(&[1, 2, 3]).unwrap();
+    s.copy_from_slice(&[1, 2, 3]).unwrap();
+    assert_eq!(q, r);
+    assert_eq!(r, q);
+    assert_eq!(q, s);
+    assert_eq!(s, q);
+    r.extract().unwrap();
+    assert_ne!(q, r);
+    s.insert(4).unwrap();
+    assert_ne!(q, s);
+    assert_ne!(s, q);
+    r.push_front(4).unwrap();
+    assert_ne!(q, r);
+}
+
+#[test]
+fn test_eq_slice() {
+    let mut q: Queue<4, usize> = Queue::default();
+    assert_eq!(q, [0usize; 0]);
+    assert_eq!([0usize; 0], q);
+    assert_eq!(q, &[0usize; 0][..]);
+    q.copy_from_slice(&[0, 0, 0]).unwrap();
+    q.drop_front(3);
+    q.copy_from_slice(&[1, 2, 3]).unwrap();
+    assert!(!q.is_contiguous());
+    assert_eq!(q, [1, 2, 3]);
+    assert_eq!([1, 2, 3], q);
+    assert_eq!(q, &[1, 2, 3][..]);
+    assert_eq!(&[1, 2, 3][..], q);
+    assert_eq!(q, *[1, 2, 3].as_slice());
+    assert_ne!(q, [1, 2]);
+    assert_ne!(q, [1, 2, 3, 4]);
+    assert_ne!(q, [1, 2, 4]);
+    assert_ne!([3, 2, 1], q);
+}
+
+#[test]
+fn test_ord() {
+    let mut q: Queue<4, usize> = Queue::default();
+    let mut r: Queue<4, usize> = Queue::default();
+    assert_eq!(Ordering::Equal, q.cmp(&r));
+    r.insert(1).unwrap();
+    assert!(q < r);
+    q.copy_from_slice(&[0, 0]).unwrap();
+    q.drop_front(2);
+    q.copy_from_slice(&[1, 2, 3]).unwrap();
+    assert!(!q.is_contiguous());
+    r.insert(2).unwrap();
+    assert!(r < q);
+    r.insert(3).unwrap();
+    assert_eq!(Ordering::Equal, q.cmp(&r));
+    r.insert(0).unwrap();
+    assert!(q < r);
+    q.insert(1).unwrap();
+    assert!(q > r);
+    assert_eq!(
+        Some(Ordering::Less),
+        f64_queue(&[1.0]).partial_cmp(&f64_queue(&[2.0]))
+    );
+    assert_eq!(None, f64_queue(&[f64::NAN]).partial_cmp(&f64_queue(&[1.0])));
+
+    fn f64_queue(values: &[f64]) -> Queue<2, f64> {
+        let mut q = Queue::default();
+        q.copy_from_slice(values).unwrap();
+        q
+    }
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_ord_model(
+        a in proptest::collection::vec(0..3u8, 0..5),
+        b in proptest::collection::vec(0..3u8, 0..5),
+        rotation in 0..5usize,
+    ) {
+        let mut q: Queue<5, u8> = Queue::default();
+        q.resize_with(rotation, Default::default).unwrap();
+        q.drop_front(rotation);
+        q.copy_from_slice(&a).unwrap();
+        let mut r: Queue<5, u8> = Queue::default();
+        r.copy_from_slice(&b).unwrap();
+        proptest::prop_assert_eq!(a.cmp(&b), q.cmp(&r));
+        proptest::prop_assert_eq!(a.partial_cmp(&b), q.partial_cmp(&r));
+    }
+}
+
+#[test]
+fn test_hash() {
+    use std::hash::DefaultHasher;
+
+    fn hash(v: impl Hash) -> u64 {
+        let mut h = DefaultHasher::new();
+        v.hash(&mut h);
+        h.finish()
+    }
+
+    let mut q: Queue<4, usize> = Queue::default();
+    let mut r: Queue<4, usize> = Queue::default();
+    assert_eq!(hash(&q), hash(&r));
+    q.copy_from_slice(&[0, 0, 0]).unwrap();
+    q.drop_front(3);
+    q.copy_from_slice(&[1, 2, 3]).unwrap();
+    assert!(!q.is_contiguous());
+    r.copy_from_slice(&[1, 2, 3]).unwrap();
+    assert_eq!(q, r);
+    assert_eq!(hash(&q), hash(&r));
+    r.extract().unwrap();
+    assert_ne!(hash(&q), hash(&r));
+
+    let empty: Queue<1, usize> = Queue::default();
+    let mut one: Queue<1, usize> = Queue::default();
+    one.insert(1).unwrap();
+    assert_ne!(hash((&one, &empty)), hash((&empty, &one)));
+}
+
+#[test]
+fn test_from_iter() {
+    let q: Queue<3, usize> = [].into_iter().collect();
+    assert!(q.is_empty());
+    let q: Queue<3, usize> = (1..=3).collect();
+    assert_eq!(q, [1, 2, 3]);
+    assert!(q.is_full());
+}
+
+#[test]
+#[should_panic(expected = "queue capacity 3 exceeded")]
+fn test_from_iter_overflow() {
+    let _: Queue<3, usize> = (0..4).collect();
+}
+
+#[test]
+fn test_try_from_iter() {
+    use core::cell::Cell;
+
+    let q = Queue::<3, usize>::try_from_iter(0..2).unwrap();
+    assert_eq!(q, [0, 1]);
+    let q = Queue::<3, usize>::try_from_iter(0..3).unwrap();
+    assert_eq!(q, [0, 1, 2]);
+    let drops = Cell::new(0);
+    let r = Queue::<3, Counted>::try_from_iter((0..5).map(|i| Counted(i, &drops)));
+    assert!(matches!(r, Err(QueueError::Overflow)));
+    assert_eq!(4, drops.get());
+
+    let q = Queue::<3, usize>::from_iter_truncated(0..2);
+    assert_eq!(q, [0, 1]);
+    let q = Queue::<3, usize>::from_iter_truncated(0..3);
+    assert_eq!(q, [0, 1, 2]);
+    let mut values = 0..5;
+    let q = Queue::<3, usize>::from_iter_truncated(&mut values);
+    assert_eq!(q, [0, 1, 2]);
+    assert_eq!(3..5, values);
+}
+
+#[test]
+fn test_extend() {
+    let mut q: Queue<4, usize> = Queue::default();
+    q.extend(0..0);
+    assert!(q.is_empty());
+    q.insert(0).unwrap();
+    q.extract().unwrap();
+    q.extend(1..3);
+    q.extend(&[3, 4]);
+    assert_eq!(q, [1, 2, 3, 4]);
+    q.extend(&[]);
+    assert!(q.is_full());
+}
+
+#[test]
+fn test_extend_overflow() {
+    let mut q: Queue<4, usize> = Queue::default();
+    q.extend([1, 2]);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| q.extend(3..6)));
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert_eq!("queue capacity 4 exceeded", message);
+    assert_eq!(q, [1, 2, 3, 4]);
+}
+
+#[test]
+fn test_try_extend() {
+    let mut q: Queue<4, usize> = Queue::default();
+    q.try_extend(0..2).unwrap();
+    q.try_extend(2..4).unwrap();
+    assert_eq!(q, [0, 1, 2, 3]);
+    q.drop_front(2);
+    let err = q.try_extend(4..9).unwrap_err();
+    assert_eq!(q, [2, 3, 4, 5]);
+    assert_eq!(6, err.value);
+    let mut rest = err.iter;
+    assert_eq!(Some(7), rest.next());
+    q.drop_front(1);
+    q.try_extend(rest).unwrap();
+    assert_eq!(q, [3, 4, 5, 8]);
+    let err = q.try_extend([9]).unwrap_err();
+    assert_eq!(9, err.value);
+    assert_eq!(None, { err.iter }.next());
+    assert!(matches!(
+        q.try_extend(0..1).map_err(QueueError::from),
+        Err(QueueError::Overflow),
+    ));
+}
+
+#[test]
+fn test_into_iter() {
+    use core::cell::Cell;
+
+    let q: Queue<3, usize> = Queue::default();
+    assert_eq!(None, q.into_iter().next());
+
+    let mut q: Queue<4, usize> = Queue::default();
+    q.copy_from_slice(&[0, 0]).unwrap();
+    q.drop_front(2);
+    q.copy_from_slice(&[1, 2, 3, 4]).unwrap();
+    let mut it = q.into_iter();
+    assert_eq!(4, it.len());
+    assert_eq!(Some(4), it.next_back());
+    assert_eq!(vec![1, 2, 3], it.collect::<Vec<_>>());
+
+    let drops = Cell::new(0);
+    let mut q: Queue<4, Counted> = Queue::default();
+    for i in 0..4 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    for v in q {
+        if v.0 == 1 {
+            break;
+        }
+    }
+    assert_eq!(4, drops.get());
+}
+
+#[test]
+fn test_into_iter_ref() {
+    fn sum<'a>(values: impl IntoIterator<Item = &'a usize>) -> usize {
+        values.into_iter().sum()
+    }
+
+    let mut q: Queue<3, usize> = Queue::default();
+    assert_eq!(0, sum(&q));
+    q.copy_from_slice(&[1, 2, 3]).unwrap();
+    q.drop_front(2);
+    q.copy_from_slice(&[4, 5]).unwrap();
+    assert_eq!(12, sum(&q));
+    assert!((&q).into_iter().eq(&[3, 4, 5]));
+}
+
+#[test]
+fn test_into_iter_mut() {
+    let mut q: Queue<4, String> = Queue::default();
+    for v in &mut q {
+        v.push('!');
+    }
+    for v in ["x", "x", "x", "a", "b", "c"] {
+        q.force_insert(v.to_string());
+    }
+    q.extract().unwrap();
+    q.insert("d".to_string()).unwrap();
+    assert!(!q.is_contiguous());
+    for v in &mut q {
+        v.push('!');
+    }
+    for v in ["a!", "b!", "c!", "d!"] {
+        assert_eq!(Some(v.to_string()), q.extract());
+    }
+}
+
+#[test]
+fn test_from_array() {
+    use core::cell::Cell;
+
+    let q: Queue<0, usize> = Queue::from([]);
+    assert!(q.is_empty());
+    let drops = Cell::new(0);
+    let mut q = Queue::<3, _>::from([Counted(0, &drops), Counted(1, &drops), Counted(2, &drops)]);
+    assert_eq!(0, drops.get());
+    assert_eq!(3, q.capacity());
+    assert_eq!(Some(0), q.extract().map(|v| v.0));
+    assert_eq!(1, drops.get());
+    drop(q);
+    assert_eq!(3, drops.get());
+}
+
+#[test]
+fn test_from_partial() {
+    let q: Queue<3, usize> = Queue::from_partial([]);
+    assert!(q.is_empty());
+    let mut q: Queue<3, usize> = Queue::from_partial([4, 5]);
+    assert_eq!(2, q.len());
+    q.insert(6).unwrap();
+    assert_eq!(q, [4, 5, 6]);
+    let q: Queue<3, usize> = Queue::from_partial([7, 8, 9]);
+    assert!(q.is_full());
+    assert_eq!(q, [7, 8, 9]);
+}
+
+#[test]
+fn test_from_fn() {
+    let q: Queue<3, usize> = Queue::from_fn(|i| 10 + i);
+    assert!(q.is_full());
+    assert_eq!(q, [10, 11, 12]);
+    let q: Queue<3, usize> = Queue::from_fn_partial(0, |_| unreachable!()).unwrap();
+    assert!(q.is_empty());
+    let mut q: Queue<3, usize> = Queue::from_fn_partial(2, |i| i).unwrap();
+    q.insert(2).unwrap();
+    assert_eq!(q, [0, 1, 2]);
+    let r = Queue::<3, usize>::from_fn_partial(4, |_| unreachable!());
+    assert!(matches!(r, Err(QueueError::Overflow)));
+
+    let drops = core::cell::Cell::new(0);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        Queue::<4, Counted>::from_fn(|i| {
+            assert!(i < 2);
+            Counted(i, &drops)
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(2, drops.get());
+
+    drops.set(0);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        Queue::<4, Counted>::from_fn_partial(3, |i| {
+            assert!(i < 1);
+            Counted(i, &drops)
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(1, drops.get());
+}
+
+#[test]
+fn test_filled() {
+    use core::cell::Cell;
+
+    #[derive(Debug)]
+    struct Cloned<'a>(&'a Cell<usize>);
+
+    impl Clone for Cloned<'_> {
+        fn clone(&self) -> Self {
+            self.0.set(self.0.get() + 1);
+            Cloned(self.0)
+        }
+    }
+
+    let clones = Cell::new(0);
+    let q: Queue<4, Cloned> = Queue::filled(Cloned(&clones));
+    assert_eq!(q.capacity(), q.len());
+    assert_eq!(3, clones.get());
+
+    clones.set(0);
+    let q: Queue<1, Cloned> = Queue::filled(Cloned(&clones));
+    assert_eq!(1, q.len());
+    assert_eq!(0, clones.get());
+
+    let drops = Cell::new(0);
+    let q: Queue<0, Counted> = Queue::filled(Counted(0, &drops));
+    assert!(q.is_empty());
+    assert_eq!(1, drops.get());
+
+    let q: Queue<3, u8> = Queue::splat(5);
+    assert_eq!(q.capacity(), q.len());
+    assert_eq!(q, [5, 5, 5]);
+}
+
+#[test]
+fn test_into_array() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<4, Counted> = Queue::new();
+    for i in 0..4 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    q.drop_front(2);
+    let mut q = q.into_array().unwrap_err();
+    assert_eq!(vec![2, 3], q.iter().map(|c| c.0).collect::<Vec<_>>());
+    assert_eq!(2, drops.get());
+
+    for i in 4..6 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    assert!(!q.is_contiguous());
+    let a = q.into_array().unwrap();
+    assert_eq!(2, drops.get());
+    assert_eq!([2, 3, 4, 5], a.each_ref().map(|c| c.0));
+    drop(a);
+    assert_eq!(6, drops.get());
+
+    let q: Queue<0, usize> = Queue::new();
+    assert_eq!([0usize; 0], q.into_array().unwrap());
+}
+
+#[test]
+fn test_grow() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<3, Counted> = Queue::new();
+    for i in 0..3 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    q.drop_front(2);
+    for i in 3..5 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    assert!(!q.is_contiguous());
+    let mut q: Queue<5, Counted> = q.grow();
+    assert_eq!(2, drops.get());
+    assert_eq!(3, q.len());
+    q.insert(Counted(5, &drops)).unwrap();
+    assert_eq!(vec![2, 3, 4, 5], q.iter().map(|c| c.0).collect::<Vec<_>>());
+    let q: Queue<5, Counted> = q.grow();
+    assert_eq!(4, q.len());
+    drop(q);
+    assert_eq!(6, drops.get());
+}
+
+#[test]
+fn test_shrink() {
+    let drops = core::cell::Cell::new(0);
+    let mut q: Queue<5, Counted> = Queue::new();
+    for i in 0..5 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    q.drop_front(3);
+    for i in 5..7 {
+        q.insert(Counted(i, &drops)).unwrap();
+    }
+    let q = q.shrink::<3>().unwrap_err();
+    assert_eq!(vec![3, 4, 5, 6], q.iter().map(|c| c.0).collect::<Vec<_>>());
+    let q: Queue<4, Counted> = q.shrink().unwrap();
+    assert_eq!(3, drops.get());
+    assert!(q.is_full());
+    assert_eq!(vec![3, 4, 5, 6], q.iter().map(|c| c.0).collect::<Vec<_>>());
+    drop(q);
+    assert_eq!(7, drops.get());
+
+    let q: Queue<3, usize> = Queue::new();
+    let q: Queue<0, usize> = q.shrink().unwrap();
+    assert!(q.is_empty());
+}
+
+#[test]
+fn test_concat() {
+    let drops = core::cell::Cell::new(0);
+    let mut a: Queue<3, Counted> = Queue::new();
+    let mut b: Queue<4, Counted> = Queue::new();
+    for i in 0..3 {
+        a.insert(Counted(i, &drops)).unwrap();
+    }
+    for i in 10..14 {
+        b.insert(Counted(i, &drops)).unwrap();
+    }
+    a.drop_front(2);
+    b.drop_front(3);
+    for i in 3..5 {
+        a.insert(Counted(i, &drops)).unwrap();
+    }
+    for i in 14..16 {
+        b.insert(Counted(i, &drops)).unwrap();
+    }
+    assert!(!a.is_contiguous());
+    assert!(!b.is_contiguous());
+    drops.set(0);
+    let q: Queue<7, Counted> = a.concat(b);
+    assert_eq!(0, drops.get());
+    assert_eq!(
+        vec![2, 3, 4, 13, 14, 15],
+        q.iter().map(|c| c.0).collect::<Vec<_>>()
+    );
+    drop(q);
+    assert_eq!(6, drops.get());
+
+    let a: Queue<2, usize> = Queue::new();
+    let b: Queue<3, usize> = Queue::from([1, 2, 3]);
+    let q: Queue<5, usize> = a.concat(b);
+    assert_eq!(q, [1, 2, 3]);
+    let b: Queue<3, usize> = Queue::new();
+    let q: Queue<8, usize> = q.concat(b);
+    assert_eq!(q, [1, 2, 3]);
+}
+
+#[test]
+fn test_queue_macro() {
+    let q: Queue<0, usize> = queue![];
+    assert!(q.is_empty());
+    let q = queue![1, 2, 3,];
+    assert_eq!(3, q.capacity());
+    assert_eq!(q, [1, 2, 3]);
+    let q = queue![cap 5; 1, 2];
+    assert_eq!(5, q.capacity());
+    assert_eq!(q, [1, 2]);
+    const N: usize = 2;
+    let q = queue![7u8; N + 1];
+    assert_eq!(3, q.capacity());
+    assert_eq!(q, [7, 7, 7]);
+}
+
+#[test]
+fn test_try_from_slice() {
+    use core::cell::Cell;
+
+    #[derive(Debug, PartialEq)]
+    struct Cloned<'a>(usize, &'a Cell<usize>);
+
+    impl Clone for Cloned<'_> {
+        fn clone(&self) -> Self {
+            self.1.set(self.1.get() + 1);
+            Cloned(self.0, self.1)
+        }
+    }
+
+    let clones = Cell::new(0);
+    let values: Vec<Cloned> = (0..4).map(|i| Cloned(i, &clones)).collect();
+    let q = Queue::<3, Cloned>::try_from(&values[..0]).unwrap();
+    assert!(q.is_empty());
+    let q = Queue::<3, Cloned>::try_from(&values[..3]).unwrap();
+    assert_eq!(q, values[..3]);
+    assert_eq!(3, clones.get());
+    clones.set(0);
+    let r = Queue::<3, Cloned>::try_from(&values[..]);
+    assert!(matches!(r, Err(QueueError::Overflow)));
+    assert_eq!(0, clones.get());
+}
+
+#[test]
+fn test_index_type() {
+    use core::mem::size_of;
+
+    assert_eq!(6, size_of::<Queue<4, u8, u8>>());
+    assert_eq!(8, size_of::<Queue<4, u8, u16>>());
+    assert_eq!(12, size_of::<Queue<4, u8, u32>>());
+    // The values are padded to the alignment of the indices.
+    assert_eq!(3 * size_of::<usize>(), size_of::<Queue<4, u8>>());
+    assert_eq!(12, size_of::<Queue<2, u32, u8>>());
+
+    // The largest capacity for the index type, wrapped.
+    let mut q: Queue<255, u8, u8> = Queue::from_fn(|i| i as u8);
+    assert_eq!(255, q.len());
+    q.drop_front(100);
+    q.copy_from_slice(&[0; 100]).unwrap();
+    assert!(q.is_full());
+    assert!(!q.is_contiguous());
+    assert_eq!(Some(100), q.extract());
+    assert_eq!(Some(&0), q.back());
+    let r: Queue<255, u8> = q.drain(..).collect();
+    assert_eq!(254, r.len());
+    assert!(r.iter().take(154).copied().eq(101..=254));
+
+    let q: Queue<3, usize, u8> = Queue::from_partial([1, 2]);
+    let r: Queue<4, usize> = Queue::from([1, 2, 0, 0]);
+    assert_ne!(q, r);
+    let q: Queue<7, usize, u8> = q.concat(r);
+    assert_eq!(q, [1, 2, 1, 2, 0, 0]);
+
+    let q: Queue<3, usize, u8> = Queue::from([1, 2, 3]);
+    assert!(q.is_full());
+    assert_eq!(q, [1, 2, 3]);
+    let q = Queue::<4, usize, u16>::try_from(&[1, 2][..]).unwrap();
+    assert_eq!(q, [1, 2]);
+    let r = Queue::<1, usize, u16>::try_from(&[1, 2][..]);
+    assert!(matches!(r, Err(QueueError::Overflow)));
+}
+
+#[test]
+fn test_wrap() {
+    fn check<const C: usize>() {
+        for i in 0..2 * C {
+            assert_eq!(i % C, Queue::<C, u8>::wrap(i));
+        }
+    }
+
+    const { assert!(Queue::<64, u8>::IS_POW2) };
+    const { assert!(!Queue::<65, u8>::IS_POW2) };
+    const { assert!(!Queue::<0, u8>::IS_POW2) };
+    check::<1>();
+    check::<3>();
+    check::<8>();
+    check::<64>();
+    check::<65>();
+}
+
+/// Operation applied by the model tests.
+#[cfg(test)]
+#[derive(Debug, Clone)]
+enum ModelOp {
+    Insert,
+    PushFront,
+    ForceInsert,
+    Extract,
+    PopBack,
+    // Drive the queue toward full or empty.
+    Fill,
+    Empty,
+    Get(usize),
+    Remove(usize),
+    InsertAt(usize),
+    Truncate(usize),
+    Clear,
+}
+
+#[cfg(test)]
+fn model_ops() -> impl proptest::strategy::Strategy<Value = Vec<ModelOp>> {
+    use proptest::prelude::*;
+
+    let op = prop_oneof![
+        4 => Just(ModelOp::Insert),
+        2 => Just(ModelOp::PushFront),
+        1 => Just(ModelOp::ForceInsert),
+        4 => Just(ModelOp::Extract),
+        2 => Just(ModelOp::PopBack),
+        1 => Just(ModelOp::Fill),
+        1 => Just(ModelOp::Empty),
+        2 => (0..10usize).prop_map(ModelOp::Get),
+        1 => (0..10usize).prop_map(ModelOp::Remove),
+        1 => (0..10usize).prop_map(ModelOp::InsertAt),
+        1 => (0..10usize).prop_map(ModelOp::Truncate),
+        1 => Just(ModelOp::Clear),
+    ];
+    proptest::collection::vec(op, 0..128)
+}
+
+/// Apply `ops` to both a queue and a capacity-limited
+/// [VecDeque](std::collections::VecDeque), checking that all
+/// observable results match and that every value created is
+/// dropped exactly once.
+#[cfg(test)]
+fn run_model<const C: usize, Ix: QueueIndex>(
+    ops: Vec<ModelOp>,
+) -> Result<(), proptest::test_runner::TestCaseError> {
+    use core::cell::Cell;
+    use proptest::prop_assert_eq;
+
+    let drops = Cell::new(0);
+    let mut created = 0;
+    {
+        let mut q: Queue<C, Counted, Ix> = Queue::default();
+        let mut model = std::collections::VecDeque::new();
+        let mut next = 0;
+        let mut value = || {
+            next += 1;
+            Counted(next, &drops)
+        };
+        for op in ops {
+            let len = model.len();
+            match op {
+                ModelOp::Insert => {
+                    let v = value();
+                    let id = v.0;
+                    prop_assert_eq!(len < C, q.insert(v).is_ok());
+                    if len < C {
+                        model.push_back(id);
+                    }
+                }
+                ModelOp::PushFront => {
+                    let v = value();
+                    let id = v.0;
+                    prop_assert_eq!(len < C, q.push_front(v).is_ok());
+                    if len < C {
+                        model.push_front(id);
+                    }
+                }
+                ModelOp::ForceInsert => {
+                    let v = value();
+                    let id = v.0;
+                    let evicted = q.force_insert(v).map(|v| v.0);
+                    if C == 0 {
+                        prop_assert_eq!(Some(id), evicted);
+                    } else {
+                        let expected = if len == C { model.pop_front() } else { None };
+                        model.push_back(id);
+                        prop_assert_eq!(expected, evicted);
+                    }
+                }
+                ModelOp::Extract => prop_assert_eq!(model.pop_front(), q.extract().map(|v| v.0)),
+                ModelOp::PopBack => prop_assert_eq!(model.pop_back(), q.pop_back().map(|v| v.0)),
+                ModelOp::Fill => {
+                    while !q.is_full() {
+                        let v = value();
+                        model.push_back(v.0);
+                        q.insert(v).unwrap();
+                    }
+                }
+                ModelOp::Empty => {
+                    while let Some(v) = q.extract() {
+                        prop_assert_eq!(model.pop_front(), Some(v.0));
+                    }
+                }
+                ModelOp::Get(i) => prop_assert_eq!(model.get(i).copied(), q.get(i).map(|v| v.0)),
+                ModelOp::Remove(i) => prop_assert_eq!(model.remove(i), q.remove(i).map(|v| v.0)),
+                ModelOp::InsertAt(i) => {
+                    let i = i % (len + 1);
+                    let v = value();
+                    let id = v.0;
+                    prop_assert_eq!(len < C, q.insert_at(i, v).is_ok());
+                    if len < C {
+                        model.insert(i, id);
+                    }
+                }
+                ModelOp::Truncate(n) => {
+                    model.truncate(n);
+                    q.truncate(n);
+                }
+                ModelOp::Clear => {
+                    model.clear();
+                    q.clear();
+                }
+            }
+            prop_assert_eq!(model.len(), q.len());
+            prop_assert_eq!(model.is_empty(), q.is_empty());
+            prop_assert_eq!(model.len() == C, q.is_full());
+            prop_assert_eq!(C - model.len(), q.remaining_capacity());
+            prop_assert_eq!(model.front().copied(), q.front().map(|v| v.0));
+            prop_assert_eq!(model.back().copied(), q.back().map(|v| v.0));
+            proptest::prop_assert!(model.iter().copied().eq(q.iter().map(|v| v.0)));
+        }
+        created += next;
+    }
+    prop_assert_eq!(created, drops.get());
+    Ok(())
+}
+
+// These run proptest's default number of cases; set
+// PROPTEST_CASES to run more.
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_model_1(ops in model_ops()) {
+        run_model::<1, usize>(ops)?;
+    }
+
+    #[test]
+    fn test_model_2(ops in model_ops()) {
+        run_model::<2, usize>(ops)?;
+    }
+
+    #[test]
+    fn test_model_5(ops in model_ops()) {
+        run_model::<5, usize>(ops)?;
+    }
+
+    #[test]
+    fn test_model_8(ops in model_ops()) {
+        run_model::<8, usize>(ops)?;
+    }
+
+    #[test]
+    fn test_model_64(ops in model_ops()) {
+        run_model::<64, usize>(ops)?;
+    }
+
+    #[test]
+    fn test_model_65(ops in model_ops()) {
+        run_model::<65, usize>(ops)?;
+    }
+
+    #[test]
+    fn test_model_5_u8(ops in model_ops()) {
+        run_model::<5, u8>(ops)?;
+    }
+
+    #[test]
+    fn test_model_8_u16(ops in model_ops()) {
+        run_model::<8, u16>(ops)?;
+    }
+
+    #[test]
+    fn test_model_255_u8(ops in model_ops()) {
+        run_model::<255, u8>(ops)?;
+    }
+}
+
+#[test]
+fn test_fmt_write() {
+    use core::fmt::Write;
+
+    let mut q: Queue<8, u8> = Queue::default();
+    q.copy_from_slice(&[0; 5]).unwrap();
+    q.drop_front(5);
+    let tag = "ab";
+    write!(q, "{}-{tag}", 12).unwrap();
+    assert!(!q.is_contiguous());
+    let mut buf = [0; 8];
+    let n = q.extract_into(&mut buf);
+    assert_eq!(b"12-ab", &buf[..n]);
+
+    q.write_str("abcdef").unwrap();
+    assert!(q.write_str("xyz").is_err());
+    assert_eq!(q, *b"abcdef");
+    q.write_str("gh").unwrap();
+    assert!(q.is_full());
+    assert!(q.write_str("").is_ok());
+
+    let mut q: Queue<4, char> = Queue::default();
+    write!(q, "é{}", 1).unwrap();
+    assert_eq!(q, ['é', '1']);
+    assert!(q.write_str("ßüñ").is_err());
+    assert_eq!(2, q.len());
+    q.write_str("ßü").unwrap();
+    assert_eq!(q, ['é', '1', 'ß', 'ü']);
+}
+
+#[test]
+fn test_new_static() {
+    use std::sync::Mutex;
+
+    static Q: Mutex<Queue<8, u8>> = Mutex::new(Queue::new());
+
+    Q.lock().unwrap().copy_from_slice(b"abc").unwrap();
+    let mut q = Q.lock().unwrap();
+    assert_eq!(Some(b'a'), q.extract());
+    assert_eq!(*q, *b"bc");
 }
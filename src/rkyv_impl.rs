@@ -0,0 +1,93 @@
+//! [rkyv] support, enabled by the `rkyv` feature. A queue
+//! is archived as an [ArchivedVec] of its values from front
+//! to back.
+
+use rkyv::{
+    rancor::{Fallible, Source},
+    ser::{Allocator, Writer},
+    vec::{ArchivedVec, VecResolver},
+    Archive, Deserialize, Place, Serialize,
+};
+
+use crate::{Queue, QueueError, QueueIndex};
+
+impl<const C: usize, T: Archive, Ix: QueueIndex> Archive for Queue<C, T, Ix> {
+    type Archived = ArchivedVec<T::Archived>;
+    type Resolver = VecResolver;
+
+    fn resolve(&self, resolver: VecResolver, out: Place<Self::Archived>) {
+        ArchivedVec::resolve_from_len(self.len(), resolver, out);
+    }
+}
+
+impl<const C: usize, T, S, Ix: QueueIndex> Serialize<S> for Queue<C, T, Ix>
+where
+    T: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<VecResolver, S::Error> {
+        ArchivedVec::<T::Archived>::serialize_from_iter::<T, _, _>(self.iter(), serializer)
+    }
+}
+
+/// Deserialization fails with [QueueError::Overflow] if the
+/// archived vector is longer than the queue capacity.
+impl<const C: usize, T, D, Ix: QueueIndex> Deserialize<Queue<C, T, Ix>, D>
+    for ArchivedVec<T::Archived>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<Queue<C, T, Ix>, D::Error> {
+        if self.len() > C {
+            return Err(D::Error::new(QueueError::Overflow));
+        }
+        let mut q = Queue::default();
+        for value in self.iter() {
+            let value = value.deserialize(deserializer)?;
+            // Safety: There are at most C values.
+            unsafe { q.push_unchecked(value) };
+        }
+        Ok(q)
+    }
+}
+
+#[test]
+fn test_rkyv() {
+    use rkyv::rancor::Error;
+
+    let mut q: Queue<4, u32> = Queue::default();
+    let bytes = rkyv::to_bytes::<Error>(&q).unwrap();
+    let r: Queue<4, u32> = rkyv::from_bytes::<_, Error>(&bytes).unwrap();
+    assert!(r.is_empty());
+    q.copy_from_slice(&[0; 3]).unwrap();
+    q.drop_front(3);
+    q.copy_from_slice(&[1, 2, 3, 4]).unwrap();
+    assert!(!q.is_contiguous());
+    let bytes = rkyv::to_bytes::<Error>(&q).unwrap();
+    let r: Queue<4, u32> = rkyv::from_bytes::<_, Error>(&bytes).unwrap();
+    assert_eq!(q, r);
+    // Zero-copy access.
+    let archived = rkyv::access::<ArchivedVec<rkyv::Archived<u32>>, Error>(&bytes).unwrap();
+    assert_eq!(4, archived.len());
+    assert!(archived.iter().map(|v| v.to_native()).eq(1..=4));
+
+    let bytes = rkyv::to_bytes::<Error>(&vec![1u32, 2, 3, 4, 5]).unwrap();
+    assert!(rkyv::from_bytes::<Queue<4, u32>, Error>(&bytes).is_err());
+    let r: Queue<5, u32> = rkyv::from_bytes::<_, Error>(&bytes).unwrap();
+    assert_eq!(r, [1, 2, 3, 4, 5]);
+
+    let mut q: Queue<3, String> = Queue::default();
+    q.insert("a".to_string()).unwrap();
+    q.extract().unwrap();
+    for v in ["b", "c", "d"] {
+        q.insert(v.to_string()).unwrap();
+    }
+    let bytes = rkyv::to_bytes::<Error>(&q).unwrap();
+    let archived = rkyv::access::<rkyv::Archived<Queue<3, String>>, Error>(&bytes).unwrap();
+    assert_eq!(["b", "c", "d"], archived.as_slice());
+    let r: Queue<3, String> = rkyv::from_bytes::<_, Error>(&bytes).unwrap();
+    assert_eq!(q, r);
+}
@@ -0,0 +1,98 @@
+//! [serde] support, enabled by the `serde` feature. A
+//! queue is serialized as a sequence of its values from
+//! front to back.
+
+use core::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{self, Deserialize, Deserializer, SeqAccess, Visitor},
+    ser::{Serialize, Serializer},
+};
+
+use crate::{Queue, QueueIndex};
+
+impl<const C: usize, T: Serialize, Ix: QueueIndex> Serialize for Queue<C, T, Ix> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self)
+    }
+}
+
+/// Deserializes a sequence directly into a queue, failing
+/// if the sequence is longer than the queue capacity.
+struct QueueVisitor<const C: usize, T, Ix: QueueIndex>(PhantomData<(T, Ix)>);
+
+impl<'de, const C: usize, T: Deserialize<'de>, Ix: QueueIndex> Visitor<'de>
+    for QueueVisitor<C, T, Ix>
+{
+    type Value = Queue<C, T, Ix>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a sequence of at most {C} values")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        if let Some(n) = seq.size_hint() {
+            if n > C {
+                return Err(de::Error::invalid_length(n, &self));
+            }
+        }
+        let mut q = Queue::default();
+        while let Some(value) = seq.next_element()? {
+            if q.is_full() {
+                return Err(de::Error::invalid_length(C + 1, &self));
+            }
+            // Safety: The queue is not full.
+            unsafe { q.push_unchecked(value) };
+        }
+        Ok(q)
+    }
+}
+
+impl<'de, const C: usize, T: Deserialize<'de>, Ix: QueueIndex> Deserialize<'de>
+    for Queue<C, T, Ix>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(QueueVisitor(PhantomData))
+    }
+}
+
+#[test]
+fn test_serde_json() {
+    let mut q: Queue<3, usize> = Queue::default();
+    assert_eq!("[]", serde_json::to_string(&q).unwrap());
+    q.copy_from_slice(&[0, 0]).unwrap();
+    q.drop_front(2);
+    q.copy_from_slice(&[1, 2, 3]).unwrap();
+    assert!(!q.is_contiguous());
+    assert_eq!("[1,2,3]", serde_json::to_string(&q).unwrap());
+
+    let r: Queue<3, usize> = serde_json::from_str("[]").unwrap();
+    assert!(r.is_empty());
+    let r: Queue<3, usize> = serde_json::from_str("[4,5]").unwrap();
+    assert_eq!(r, [4, 5]);
+    let r: Queue<3, usize> = serde_json::from_str("[1,2,3]").unwrap();
+    assert_eq!(q, r);
+    let err = serde_json::from_str::<Queue<3, usize>>("[1,2,3,4]").unwrap_err();
+    assert!(err.to_string().contains("a sequence of at most 3 values"));
+}
+
+#[test]
+fn test_serde_postcard() {
+    let mut buf = [0u8; 16];
+    let mut q: Queue<3, u16> = Queue::default();
+    let bytes = postcard::to_slice(&q, &mut buf).unwrap();
+    let r: Queue<3, u16> = postcard::from_bytes(bytes).unwrap();
+    assert!(r.is_empty());
+    q.copy_from_slice(&[0, 0]).unwrap();
+    q.drop_front(1);
+    q.copy_from_slice(&[300, 2]).unwrap();
+    let bytes = postcard::to_slice(&q, &mut buf).unwrap();
+    let r: Queue<3, u16> = postcard::from_bytes(bytes).unwrap();
+    assert_eq!(r, [0, 300, 2]);
+    let bytes = postcard::to_slice(&[1u16, 2][..], &mut buf).unwrap();
+    let r: Queue<3, u16> = postcard::from_bytes(bytes).unwrap();
+    assert_eq!(r, [1, 2]);
+
+    let bytes = postcard::to_slice(&[1u16, 2, 3, 4][..], &mut buf).unwrap();
+    assert!(postcard::from_bytes::<Queue<3, u16>>(bytes).is_err());
+}
@@ -0,0 +1,84 @@
+//! [smallvec] support, enabled by the `smallvec` feature.
+
+use smallvec::SmallVec;
+
+use crate::{InsertError, Queue, QueueIndex};
+
+/// Move the values of a queue into a [SmallVec] with the same
+/// inline capacity, from front to back. The values always
+/// fit inline, so nothing is allocated.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// use smallvec::SmallVec;
+///
+/// let mut q: Queue<3, usize> = Queue::default();
+/// q.copy_from_slice(&[1, 2]).unwrap();
+/// let v = SmallVec::from(q);
+/// assert_eq!(&[1, 2], v.as_slice());
+/// assert!(!v.spilled());
+/// ```
+impl<const C: usize, T, Ix: QueueIndex> From<Queue<C, T, Ix>> for SmallVec<[T; C]> {
+    fn from(q: Queue<C, T, Ix>) -> Self {
+        let mut vec = SmallVec::new();
+        vec.extend(q);
+        vec
+    }
+}
+
+/// Move the values of a [SmallVec] of any inline capacity
+/// into a queue, in order.
+///
+/// # Errors
+///
+/// Returns [InsertError] holding the unchanged vector if it is
+/// longer than the queue capacity.
+///
+/// # Examples
+///
+/// ```
+/// # use smallqueue::Queue;
+/// use smallvec::{smallvec, SmallVec};
+///
+/// let v: SmallVec<[usize; 2]> = smallvec![1, 2, 3];
+/// let q = Queue::<3, usize>::try_from(v).unwrap();
+/// assert_eq!(q, [1, 2, 3]);
+/// ```
+impl<const C: usize, const N: usize, T, Ix: QueueIndex> TryFrom<SmallVec<[T; N]>>
+    for Queue<C, T, Ix>
+{
+    type Error = InsertError<SmallVec<[T; N]>>;
+
+    fn try_from(vec: SmallVec<[T; N]>) -> Result<Self, Self::Error> {
+        if vec.len() > C {
+            return Err(InsertError(vec));
+        }
+        let mut q = Queue::default();
+        q.extend(vec);
+        Ok(q)
+    }
+}
+
+#[test]
+fn test_smallvec() {
+    let mut q: Queue<4, String> = Queue::default();
+    for v in ["x", "y", "a", "b", "c", "d"] {
+        q.force_insert(v.to_string());
+    }
+    assert!(!q.is_contiguous());
+    let v = SmallVec::from(q);
+    assert!(!v.spilled());
+    assert_eq!(&["a", "b", "c", "d"], v.as_slice());
+
+    let q = Queue::<4, String>::try_from(v).unwrap();
+    assert_eq!(q, ["a", "b", "c", "d"]);
+    let mut v = SmallVec::from(q);
+    v.push("e".to_string());
+    assert!(v.spilled());
+    let v = Queue::<4, String>::try_from(v).unwrap_err().into_inner();
+    assert_eq!(&["a", "b", "c", "d", "e"], v.as_slice());
+    let q = Queue::<5, String>::try_from(v).unwrap();
+    assert_eq!(q, ["a", "b", "c", "d", "e"]);
+}
@@ -0,0 +1,60 @@
+//! [std] support, enabled by the `std` feature.
+
+use std::io;
+
+use crate::{Queue, QueueIndex};
+
+/// Reads bytes from the front of the queue. Reading from an
+/// empty queue returns `Ok(0)`.
+impl<const C: usize, Ix: QueueIndex> io::Read for Queue<C, u8, Ix> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        Ok(self.extract_into(buf))
+    }
+}
+
+/// Writes as many bytes as fit into the back of the queue,
+/// returning the number written. Writing to a full queue
+/// returns `Ok(0)` rather than blocking.
+impl<const C: usize, Ix: QueueIndex> io::Write for Queue<C, u8, Ix> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.remaining_capacity());
+        // Safety: There is room for the first n bytes of buf,
+        // which is borrowed separately.
+        unsafe { self.put_back(buf.as_ptr(), n) };
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_io() {
+    use std::io::{Read, Write};
+
+    let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+    let mut q: Queue<7, u8> = Queue::default();
+    let mut src = &data[..];
+    let mut out = Vec::new();
+    let mut buf = [0; 5];
+    while !src.is_empty() || !q.is_empty() {
+        let n = q.write(src).unwrap();
+        src = &src[n..];
+        q.flush().unwrap();
+        let n = q.read(&mut buf).unwrap();
+        out.extend_from_slice(&buf[..n]);
+    }
+    assert_eq!(data, out);
+
+    q.write_all(b"abcdefg").unwrap();
+    assert_eq!(0, q.write(b"h").unwrap());
+    assert_eq!(
+        io::ErrorKind::WriteZero,
+        q.write_all(b"h").unwrap_err().kind()
+    );
+    let mut s = String::new();
+    q.read_to_string(&mut s).unwrap();
+    assert_eq!("abcdefg", s);
+    assert_eq!(0, q.read(&mut buf).unwrap());
+}
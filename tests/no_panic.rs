@@ -0,0 +1,75 @@
+//! Check that the optimized [Queue::insert] and
+//! [Queue::extract] contain no panic path. Each call is
+//! wrapped in a guard whose drop, reached only by unwinding,
+//! calls a function that does not exist. If the optimizer
+//! cannot remove every panic, linking fails with an error
+//! naming the function.
+//!
+//! Unoptimized builds keep their panic paths, so this test
+//! only runs with `cargo test --release`.
+
+#![cfg(not(debug_assertions))]
+
+use std::hint::black_box;
+
+use smallqueue::{InsertError, Queue, QueueIndex};
+
+struct NoPanic;
+
+extern "C" {
+    #[link_name = "\n\nERROR: a Queue operation contains a panic path\n\n"]
+    fn panic_path_found() -> !;
+}
+
+impl Drop for NoPanic {
+    fn drop(&mut self) {
+        // Safety: Never called unless linking has already
+        // failed.
+        unsafe { panic_path_found() }
+    }
+}
+
+#[inline(never)]
+fn insert<const C: usize, T, Ix: QueueIndex>(
+    q: &mut Queue<C, T, Ix>,
+    value: T,
+) -> Result<(), InsertError<T>> {
+    let guard = NoPanic;
+    let result = q.insert(value);
+    core::mem::forget(guard);
+    result
+}
+
+#[inline(never)]
+fn extract<const C: usize, T, Ix: QueueIndex>(q: &mut Queue<C, T, Ix>) -> Option<T> {
+    let guard = NoPanic;
+    let result = q.extract();
+    core::mem::forget(guard);
+    result
+}
+
+fn round_trip<const C: usize, Ix: QueueIndex>() {
+    let mut q: Queue<C, u32, Ix> = Queue::new();
+    for i in 0..2 * C as u32 {
+        if insert(black_box(&mut q), i).is_err() {
+            assert_eq!(Some(i - C as u32), extract(black_box(&mut q)));
+            insert(&mut q, i).unwrap();
+        }
+    }
+    while extract(black_box(&mut q)).is_some() {}
+}
+
+#[test]
+fn test_no_panic() {
+    round_trip::<1, usize>();
+    round_trip::<5, usize>();
+    round_trip::<8, usize>();
+    round_trip::<100, usize>();
+    round_trip::<5, u8>();
+    round_trip::<255, u8>();
+    round_trip::<8, u16>();
+
+    let mut q: Queue<3, String> = Queue::new();
+    insert(&mut q, "a".to_string()).unwrap();
+    assert_eq!(Some("a".to_string()), extract(&mut q));
+}